@@ -0,0 +1,152 @@
+//! Generates the registry of solutions that are linked into the `all` binary.
+//! See `src/template/registry.rs`.
+//!
+//! Also records the compiler and its codegen flags for benchmarks, see
+//! `src/template/environment.rs`.
+use std::{env, fmt::Write, fs, path::Path, process::Command};
+
+fn main() {
+    println!("cargo::rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut names: Vec<String> = vec![];
+
+    // NOTE: every solution installs its own global allocator when profiling with dhat,
+    // so solutions can only be linked into one binary without it.
+    if env::var_os("CARGO_FEATURE_DHAT_HEAP").is_none() {
+        names = fs::read_dir(&bin_dir)
+            .unwrap()
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .filter_map(|file| file.strip_suffix(".rs").map(String::from))
+            .filter(|name| is_solution(name))
+            .collect();
+        names.sort();
+    }
+
+    let mut registry = String::new();
+
+    for name in &names {
+        let path = bin_dir.join(format!("{name}.rs"));
+        writeln!(registry, "#[allow(dead_code)]").unwrap();
+        writeln!(registry, "#[path = {:?}]", path.display().to_string()).unwrap();
+        writeln!(registry, "mod solution_{name};").unwrap();
+    }
+
+    writeln!(registry, "\nconst SOLUTIONS: &[Solution] = &[").unwrap();
+    for name in &names {
+        writeln!(
+            registry,
+            "    Solution {{ day: solution_{name}::DAY, variant: solution_{name}::VARIANT, run: solution_{name}::run }},"
+        )
+        .unwrap();
+    }
+    writeln!(registry, "];").unwrap();
+
+    record_toolchain();
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), registry).unwrap();
+    fs::write(
+        Path::new(&out_dir).join("variants.rs"),
+        variants(&bin_dir, &names),
+    )
+    .unwrap();
+}
+
+/// Passes the compiler version and the `target-cpu` and `target-feature` codegen flags
+/// to the crate, as `AOC_RUSTC_VERSION` and `AOC_TARGET_FLAGS`.
+fn record_toolchain() {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+
+    if let Some(version) = version {
+        println!("cargo::rustc-env=AOC_RUSTC_VERSION={version}");
+    }
+
+    // NOTE: flags are separated by 0x1f, and `-C` may be followed by its value as a separate flag.
+    let rustflags = env::var("CARGO_ENCODED_RUSTFLAGS").unwrap_or_default();
+    let flags: Vec<&str> = rustflags
+        .split('\x1f')
+        .filter(|flag| *flag != "-C")
+        .map(|flag| flag.trim_start_matches("-C"))
+        .filter(|flag| flag.starts_with("target-cpu=") || flag.starts_with("target-feature="))
+        .collect();
+
+    println!("cargo::rustc-env=AOC_TARGET_FLAGS={}", flags.join(" "));
+}
+
+/// Generates the candidates of the differential tests, see `src/template/differential.rs`.
+/// Only days with more than one solution are compared.
+fn variants(bin_dir: &Path, names: &[String]) -> String {
+    let base = |name: &str| parse_solution(name).map(|(base, _)| base.to_string());
+    let compared: Vec<&String> = names
+        .iter()
+        .filter(|name| {
+            names
+                .iter()
+                .filter(|other| base(other) == base(name))
+                .count()
+                > 1
+        })
+        .collect();
+
+    let mut variants = String::new();
+
+    for name in &compared {
+        let path = bin_dir.join(format!("{name}.rs"));
+        writeln!(variants, "#[allow(dead_code)]").unwrap();
+        writeln!(variants, "#[path = {:?}]", path.display().to_string()).unwrap();
+        writeln!(variants, "mod solution_{name};").unwrap();
+    }
+
+    writeln!(variants, "\nconst CANDIDATES: &[Candidate] = &[").unwrap();
+    for name in &compared {
+        writeln!(
+            variants,
+            "    Candidate {{ day: solution_{name}::DAY, variant: solution_{name}::VARIANT, \
+             part_one: |input, ctx| solution_{name}::answer_part(input, ctx, 1), \
+             part_two: |input, ctx| solution_{name}::answer_part(input, ctx, 2) }},"
+        )
+        .unwrap();
+    }
+    writeln!(variants, "];").unwrap();
+
+    variants
+}
+
+/// Matches the binary names of solutions and their variants, e.g. `01`, `2024_01` or `08_fast`.
+fn is_solution(name: &str) -> bool {
+    parse_solution(name).is_some()
+}
+
+/// Splits the binary name of a solution into the binary name of its day and its variant,
+/// e.g. `08_fast` into `08` and `fast`.
+fn parse_solution(name: &str) -> Option<(&str, Option<&str>)> {
+    let is_number = |s: &str, len: usize| s.len() == len && s.bytes().all(|b| b.is_ascii_digit());
+    let is_variant = |s: &str| s.starts_with(|c: char| c.is_ascii_lowercase());
+
+    let is_year = name.get(..4).is_some_and(|year| is_number(year, 4));
+    let base_len = if is_year && name.get(4..5) == Some("_") {
+        7
+    } else {
+        2
+    };
+    let (base, rest) = name.split_at_checked(base_len)?;
+
+    if !is_number(&base[base_len - 2..], 2) {
+        return None;
+    }
+
+    match rest.strip_prefix('_') {
+        None if rest.is_empty() => Some((base, None)),
+        Some(variant) if is_variant(variant) => Some((base, Some(variant))),
+        _ => None,
+    }
+}
@@ -73,7 +73,6 @@ fn parse_line(line: &str) -> i64 {
 }
 
 #[cfg(test)]
-
 mod tests {
     use super::*;
 
@@ -1,24 +1,32 @@
 use std::ops::RangeInclusive;
 
+use advent_of_code::parse::{ParseError, number, records, split_pair};
+
 advent_of_code::solution!(2);
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let answer = parse_input(input).flat_map(iter_invalid_half_ids).sum();
-    Some(answer)
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let answer = parse_input(input)?
+        .into_iter()
+        .flat_map(iter_invalid_half_ids)
+        .sum();
+    Ok(answer)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let answer = parse_input(input).flat_map(iter_range_invalid_ids).sum();
-    Some(answer)
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let answer = parse_input(input)?
+        .into_iter()
+        .flat_map(iter_range_invalid_ids)
+        .sum();
+    Ok(answer)
 }
 
-fn parse_input(input: &str) -> impl Iterator<Item = RangeInclusive<u64>> {
-    input.split(',').map(|range| {
-        let (a, b) = range.split_once('-').unwrap();
-
-        // Trimming whitespace is important here!
-        a.trim().parse().unwrap()..=b.trim().parse().unwrap()
-    })
+fn parse_input(input: &str) -> Result<Vec<RangeInclusive<u64>>, ParseError> {
+    records(input, ',')
+        .map(|range| {
+            let (a, b) = split_pair(input, range, '-')?;
+            Ok(number(input, a)?..=number(input, b)?)
+        })
+        .collect()
 }
 
 /* === Part 1 === */
@@ -141,13 +149,19 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1227775554));
+        assert_eq!(result, Ok(1227775554));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4174379265));
+        assert_eq!(result, Ok(4174379265));
+    }
+
+    #[test]
+    fn test_malformed_range() {
+        let error = part_one("11-22,33x44\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 7));
     }
 
     #[test]
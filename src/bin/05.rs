@@ -1,84 +1,68 @@
-use std::{
-    cmp::{max, min},
-    ops::RangeInclusive,
-    str::Lines,
+use std::ops::RangeInclusive;
+
+use advent_of_code::{
+    ds::IntervalSet,
+    parse::{self, ParseError, number, sections, split_pair},
 };
 
 advent_of_code::solution!(5);
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let mut lines = input.lines();
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let (ranges, ids) = split_sections(input)?;
 
-    let ranges = create_disjoint_ranges(iter_ranges(&mut lines));
-    let ids = iter_ids(&mut lines);
+    let ranges: IntervalSet = iter_ranges(input, ranges).collect::<Result<_, _>>()?;
+    let mut answer = 0;
 
     // Count how many numbers fall within any of the given ranges
-    let answer = ids.filter(|n| ranges.iter().any(|r| r.contains(n))).count() as u64;
+    for id in iter_ids(input, ids) {
+        if ranges.contains(id?) {
+            answer += 1;
+        }
+    }
 
-    Some(answer)
+    Ok(answer)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut lines = input.lines();
-
-    let ranges = create_disjoint_ranges(iter_ranges(&mut lines));
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let (ranges, _) = split_sections(input)?;
 
-    // Now calculate the total covered space by the disjoint ranges
-    let answer = ranges.iter().map(|r| r.end() - r.start() + 1).sum();
+    let ranges: IntervalSet = iter_ranges(input, ranges).collect::<Result<_, _>>()?;
 
-    Some(answer)
+    // Merging the ranges into disjoint ones makes the covered space simple to count
+    Ok(ranges.total_len())
 }
 
-/// Merges overlapping ranges into disjoint ranges.
-fn create_disjoint_ranges(
-    ranges: impl Iterator<Item = RangeInclusive<u64>>,
-) -> Vec<RangeInclusive<u64>> {
-    let mut disjoint_ranges = Vec::new();
-
-    for mut range in ranges {
-        let mut cursor = 0;
-
-        while let Some(target) = disjoint_ranges.get(cursor) {
-            if are_disjoint(&range, target) {
-                cursor += 1;
-                continue;
-            }
-
-            // Merge the two ranges with a simple min/max
-            let start = min(*range.start(), *target.start());
-            let end = max(*range.end(), *target.end());
-            range = start..=end;
-
-            // Remove the merged range, but do not advance the cursor
-            disjoint_ranges.swap_remove(cursor);
-        }
-
-        // Add the (possibly merged) range to the list
-        disjoint_ranges.push(range);
+/// Splits the input into the section of ranges and the section of IDs.
+fn split_sections(input: &str) -> Result<(&str, &str), ParseError> {
+    let mut sections = sections(input);
+
+    match (sections.next(), sections.next()) {
+        (Some(ranges), Some(ids)) => Ok((ranges, ids)),
+        _ => Err(ParseError::at(
+            input,
+            input.len(),
+            "expected ranges and IDs separated by a blank line",
+        )),
     }
-
-    disjoint_ranges
 }
 
-/// Returns true if the two ranges do not overlap (or touch) at all.
-fn are_disjoint(a: &RangeInclusive<u64>, b: &RangeInclusive<u64>) -> bool {
-    a.end() < b.start() || b.end() < a.start()
-}
-
-/// Parses the input lines into ranges until an empty line is encountered.
-fn iter_ranges<'a>(lines: &'a mut Lines) -> impl Iterator<Item = RangeInclusive<u64>> + 'a {
-    lines.take_while(|line| !line.is_empty()).map(|line| {
-        let (a, b) = line.split_once('-').unwrap();
-        let start = a.parse().unwrap();
-        let end = b.parse().unwrap();
-
-        start..=end
+/// Parses the section of ranges.
+fn iter_ranges<'a>(
+    input: &'a str,
+    ranges: &'a str,
+) -> impl Iterator<Item = Result<RangeInclusive<u64>, ParseError>> + 'a {
+    parse::records(ranges, '\n').map(|line| {
+        let (a, b) = split_pair(input, line, '-')?;
+        Ok(number(input, a)?..=number(input, b)?)
     })
 }
 
-/// Parses the remaining input lines into IDs.
-fn iter_ids(lines: &mut Lines) -> impl Iterator<Item = u64> {
-    lines.map(|id| id.parse().unwrap())
+/// Parses the section of IDs.
+fn iter_ids<'a>(
+    input: &'a str,
+    ids: &'a str,
+) -> impl Iterator<Item = Result<u64, ParseError>> + 'a {
+    parse::records(ids, '\n').map(|id| number(input, id))
 }
 
 #[cfg(test)]
@@ -88,12 +72,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3));
+        assert_eq!(result, Ok(3));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(14));
+        assert_eq!(result, Ok(14));
     }
 }
@@ -2,9 +2,6 @@ use itertools::izip;
 
 advent_of_code::solution!(6);
 
-const N_COLS: usize = 1000;
-const N_ROWS: usize = 4;
-
 pub fn part_one(input: &str) -> Option<u64> {
     let (mut data, ops) = parse_input_1(input);
     let answer = do_homework_1(&mut data, &ops);
@@ -34,7 +31,7 @@ impl Operation {
 /* -- Part 2 -- */
 
 fn parse_input_1(input: &str) -> (Vec<u64>, Vec<Operation>) {
-    let mut data = Vec::with_capacity(N_ROWS * N_COLS);
+    let mut data = Vec::new();
     let mut ops = Vec::new();
 
     for line in input.lines() {
@@ -92,7 +89,7 @@ fn parse_input_2(input: &str) -> (Vec<&[u8]>, impl Iterator<Item = Operation>) {
 
 fn do_homework_2(lines: &mut [&[u8]], ops: impl Iterator<Item = Operation>) -> u64 {
     let mut answer = 0;
-    let mut numbers = Vec::with_capacity(N_ROWS);
+    let mut numbers = Vec::with_capacity(lines.len());
 
     for op in ops {
         loop {
@@ -100,7 +97,7 @@ fn do_homework_2(lines: &mut [&[u8]], ops: impl Iterator<Item = Operation>) -> u
 
             for line in &mut *lines {
                 if let Some(digit) = line.split_off_first()
-                    && (b'0'..=b'9').contains(&digit)
+                    && digit.is_ascii_digit()
                 {
                     number = 10 * number + (digit - b'0') as u64;
                 }
@@ -1,55 +1,54 @@
+use advent_of_code::{grid::GridView, parse::ParseError};
+
 advent_of_code::solution!(7);
 
-pub fn part_one(input: &str) -> Option<u64> {
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
     let (splits, _) = simulate_tachyon_manifold(input)?;
-    Some(splits)
+    Ok(splits)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
     let (_, timelines) = simulate_tachyon_manifold(input)?;
-    Some(timelines)
+    Ok(timelines)
 }
 
-fn simulate_tachyon_manifold(input: &str) -> Option<(u64, u64)> {
-    let mut input = input.bytes();
+fn simulate_tachyon_manifold(input: &str) -> Result<(u64, u64), ParseError> {
+    let manifold = GridView::new(input)?;
 
-    let start_idx = input.position(|x| x == b'S')?;
-    let line_length = 1 + start_idx + input.position(|x| x == b'\n')?;
+    let start = manifold
+        .row(0)
+        .iter()
+        .position(|&b| b == b'S')
+        .ok_or_else(|| ParseError::at(input, 0, "expected a start `S` in the first row"))?;
 
-    let mut beams = vec![0; line_length];
-    let mut cursor = 0;
+    // The beams are padded by one column on each side, so splitters on the edges never go OOB
+    let mut beams = vec![0; manifold.width() + 2];
     let mut splits = 0;
 
-    // SAFETY: start_idx is guaranteed to be within bounds
-    unsafe { *beams.get_unchecked_mut(start_idx) = 1 };
+    beams[start + 1] = 1;
+
+    for row in manifold.rows().skip(1) {
+        for (x, _) in row.iter().enumerate().filter(|(_, b)| **b == b'^') {
+            let cursor = x + 1;
 
-    for char in input {
-        if char == b'^' {
-            // SAFETY: each line has the same length, this will never go OOB
-            let timelines = unsafe { *beams.get_unchecked(cursor) };
+            // SAFETY: cursor - 1 and cursor + 1 are within the padded beams
+            unsafe {
+                let timelines = *beams.get_unchecked(cursor);
 
-            if timelines > 0 {
-                splits += 1;
+                if timelines > 0 {
+                    splits += 1;
 
-                // SAFETY: the input is padded with walls, so we will never go OOB
-                unsafe {
                     *beams.get_unchecked_mut(cursor - 1) += timelines;
                     *beams.get_unchecked_mut(cursor) = 0;
                     *beams.get_unchecked_mut(cursor + 1) += timelines;
                 }
             }
         }
-
-        cursor += 1;
-
-        if char == b'\n' {
-            cursor = 0;
-        }
     }
 
     let timelines = beams.iter().sum();
 
-    Some((splits, timelines))
+    Ok((splits, timelines))
 }
 
 #[cfg(test)]
@@ -59,12 +58,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(21));
+        assert_eq!(result, Ok(21));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(40));
+        assert_eq!(result, Ok(40));
     }
 }
@@ -1,4 +1,7 @@
-use std::{cmp::Reverse, collections::BinaryHeap};
+use advent_of_code::{
+    ds::BoundedHeap,
+    parse::{ParseError, numbers, records},
+};
 
 advent_of_code::solution!(9);
 
@@ -8,21 +11,19 @@ advent_of_code::solution!(9);
 /// the algorithm will return 0.
 const MAX_CANDIDATES: usize = 65536;
 
-const NUMBER_POINTS: usize = 496;
-
-pub fn part_one(input: &str) -> Option<u64> {
-    let points = parse_input(input);
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let points = parse_input(input)?;
     let answer = solve_part_one(points);
 
-    Some(answer)
+    Ok(answer)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let points = parse_input(input);
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let points = parse_input(input)?;
     let edges = Edges::build(&points);
     let answer = solve_part_two(points, edges);
 
-    Some(answer)
+    Ok(answer)
 }
 
 /* === Parsing === */
@@ -33,21 +34,22 @@ struct Points {
     y: Box<[i32]>,
 }
 
-fn parse_input(input: &str) -> Points {
-    let mut x = Vec::with_capacity(NUMBER_POINTS);
-    let mut y = Vec::with_capacity(NUMBER_POINTS);
+fn parse_input(input: &str) -> Result<Points, ParseError> {
+    let number_points = input.lines().count();
+    let mut x = Vec::with_capacity(number_points);
+    let mut y = Vec::with_capacity(number_points);
 
-    for line in input.lines() {
-        let (a, b) = line.split_once(',').unwrap();
+    for line in records(input, '\n') {
+        let [a, b] = numbers(input, line, ',')?;
 
-        x.push(a.parse().unwrap());
-        y.push(b.parse().unwrap());
+        x.push(a);
+        y.push(b);
     }
 
-    Points {
+    Ok(Points {
         x: x.into_boxed_slice(),
         y: y.into_boxed_slice(),
-    }
+    })
 }
 
 /* === Part 1 === */
@@ -76,8 +78,8 @@ fn solve_part_two(points: Points, edges: Edges) -> u64 {
     // Optimises away bound checks in the loop without unsafe code (small cost)
     let n = points.x.len().min(points.y.len());
 
-    // Use a min-heap of size MAX_CANDIDATES to keep top candidates
-    let mut heap = BinaryHeap::<Reverse<(u64, usize, usize)>>::with_capacity(MAX_CANDIDATES);
+    // Keep the MAX_CANDIDATES largest areas as candidates
+    let mut heap = BoundedHeap::new(MAX_CANDIDATES);
 
     // Similar to part one, but we keep a bounded heap of largest areas
     for i in 0..n {
@@ -86,24 +88,12 @@ fn solve_part_two(points: Points, edges: Edges) -> u64 {
             let dy = points.y[i].abs_diff(points.y[j]) as u64 + 1;
             let area = dx * dy;
 
-            if heap.len() < MAX_CANDIDATES {
-                heap.push(Reverse((area, i, j)));
-            } else if let Some(&Reverse((min_area, _, _))) = heap.peek() {
-                if area > min_area {
-                    heap.pop();
-                    heap.push(Reverse((area, i, j)));
-                }
-            }
+            heap.push(area, (i, j));
         }
     }
 
-    // Drain the heap into a vector...
-    let mut candidates: Vec<_> = heap.into_iter().map(|Reverse(x)| x).collect();
-
-    // ...and sort by area descending
-    candidates.sort_unstable_by(|a, b| b.0.cmp(&a.0));
-
-    for (area, i, j) in candidates {
+    // Try the candidates by area descending
+    for (area, (i, j)) in heap.into_sorted_vec() {
         let (&x1, &x2) = unsafe { (points.x.get_unchecked(i), points.x.get_unchecked(j)) };
         let (&y1, &y2) = unsafe { (points.y.get_unchecked(i), points.y.get_unchecked(j)) };
 
@@ -236,12 +226,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(50));
+        assert_eq!(result, Ok(50));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(24));
+        assert_eq!(result, Ok(24));
     }
 }
@@ -1,3 +1,6 @@
+use std::fmt::{self, Display};
+
+use advent_of_code::parse::{ParseError, Scanner, number, records};
 use good_lp::{
     Expression, ProblemVariables, Solution, SolverModel, constraint, default_solver, variable,
 };
@@ -5,26 +8,32 @@ use pathfinding::prelude::*;
 
 advent_of_code::solution!(10);
 
-pub fn part_one(input: &str) -> Option<u64> {
+pub fn part_one(input: &str) -> Result<u64, Error> {
     let mut answer = 0;
 
     for machine in parse_input(input) {
+        let (line, machine) = machine?;
         let path = bfs(
             &0,
             |&p| machine.buttons.iter().map(move |&b| p ^ b),
             |&p| p == machine.pattern,
-        );
+        )
+        .ok_or_else(|| Error::Unsolvable {
+            line,
+            reason: "the buttons cannot light the pattern".into(),
+        })?;
 
-        answer += path.unwrap().len() as u64 - 1;
+        answer += path.len() as u64 - 1;
     }
 
-    Some(answer)
+    Ok(answer)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(input: &str) -> Result<u64, Error> {
     let mut answer = 0;
 
     for machine in parse_input(input) {
+        let (line, machine) = machine?;
         let n_buttons = machine.buttons.len();
         let n_joltages = machine.joltage.len();
 
@@ -55,13 +64,42 @@ pub fn part_two(input: &str) -> Option<u64> {
             problem.add_constraint(constraint);
         }
 
-        let solution = problem.solve().unwrap();
+        let solution = problem.solve().map_err(|e| Error::Unsolvable {
+            line,
+            reason: format!("the joltages cannot be reached: {e}"),
+        })?;
         let total = x.iter().map(|&v| solution.value(v)).sum::<f64>();
 
         answer += total.round() as u64;
     }
 
-    Some(answer)
+    Ok(answer)
+}
+
+/// Why a part has no answer.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Parse(ParseError),
+    /// The machine on the 1-based `line` cannot be configured.
+    Unsolvable {
+        line: usize,
+        reason: String,
+    },
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "{e}"),
+            Error::Unsolvable { line, reason } => write!(f, "line {line}: {reason}"),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -71,42 +109,63 @@ struct Machine {
     pattern: u16,
 }
 
-fn parse_input(input: &str) -> impl Iterator<Item = Machine> {
-    input.lines().map(|line| {
-        let mut buttons = Vec::new();
-
-        let mut parts = line.split_ascii_whitespace();
-        let pattern_str = parts.next().unwrap();
-
-        let pattern_len = (pattern_str.len() - 2) as u16;
-        let pattern = pattern_str[1..pattern_str.len() - 1]
-            .chars()
-            .fold(0, |acc, s| acc << 1 | (s == '#') as u16);
-
-        for part in parts {
-            if part.starts_with('(') {
-                let sequence = part[1..part.len() - 1].split(',').fold(0, |acc, s| {
-                    let i = s.parse::<u16>().unwrap();
-                    acc | 1 << (pattern_len - 1 - i)
-                });
-
-                buttons.push(sequence);
-            } else {
-                let joltage = part[1..part.len() - 1]
-                    .split(',')
-                    .map(|s| s.parse::<u16>().unwrap())
-                    .collect::<Vec<_>>()
-                    .into_boxed_slice();
-
-                return Machine {
-                    buttons: buttons.into_boxed_slice(),
-                    joltage,
-                    pattern,
-                };
+/// Parses the machines along with the number of the line they are specified on.
+fn parse_input(input: &str) -> impl Iterator<Item = Result<(usize, Machine), ParseError>> {
+    input
+        .lines()
+        .zip(1..)
+        .map(|(line, number)| (line.trim(), number))
+        .filter(|(line, _)| !line.is_empty())
+        .map(|(line, number)| Ok((number, parse_machine(input, line)?)))
+}
+
+/// Parses a machine like `[.##.] (3) (1,3) (2) {3,5,4,7}`.
+fn parse_machine(input: &str, line: &str) -> Result<Machine, ParseError> {
+    let mut scanner = Scanner::within(input, line);
+
+    let lights = scanner.delimited(b'[', b']')?;
+    let pattern_len = lights.len() as u16;
+    let pattern = lights
+        .bytes()
+        .fold(0, |acc, b| acc << 1 | (b == b'#') as u16);
+
+    let mut buttons = Vec::new();
+
+    loop {
+        scanner.skip_whitespace();
+
+        if scanner.peek() == Some(b'{') {
+            break;
+        }
+
+        let mut sequence = 0;
+
+        for light in records(scanner.delimited(b'(', b')')?, ',') {
+            let i: u16 = number(input, light)?;
+
+            if i >= pattern_len {
+                return Err(ParseError::at_slice(
+                    input,
+                    light,
+                    format!("expected a light below {pattern_len}"),
+                ));
             }
+
+            sequence |= 1 << (pattern_len - 1 - i);
         }
 
-        unreachable!()
+        buttons.push(sequence);
+    }
+
+    let joltage = records(scanner.delimited(b'{', b'}')?, ',')
+        .map(|joltage| number(input, joltage))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_boxed_slice();
+
+    Ok(Machine {
+        buttons: buttons.into_boxed_slice(),
+        joltage,
+        pattern,
     })
 }
 
@@ -117,12 +176,21 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(7));
+        assert_eq!(result, Ok(7));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(33));
+        assert_eq!(result, Ok(33));
+    }
+
+    #[test]
+    fn test_unreachable_pattern() {
+        let error = part_one("\n[.#] (0) {1,1}\n").unwrap_err();
+        assert!(
+            matches!(error, Error::Unsolvable { line: 2, .. }),
+            "{error}"
+        );
     }
 }
@@ -1,29 +1,54 @@
 use std::collections::HashMap;
+use std::fmt::{self, Display};
 
+use advent_of_code::parse::{ParseError, records, split_pair};
 use pathfinding::prelude::count_paths;
 
 advent_of_code::solution!(11);
 
 type Id = u16;
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let graph = Graph::parse(input);
-    let n_paths = graph.paths("you", "out");
-    Some(n_paths as u64)
+pub fn part_one(input: &str) -> Result<u64, Error> {
+    let graph = Graph::parse(input)?;
+    let n_paths = graph.paths("you", "out")?;
+    Ok(n_paths)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let graph = Graph::parse(input);
+pub fn part_two(input: &str) -> Result<u64, Error> {
+    let graph = Graph::parse(input)?;
 
     // Count paths: svr → dac → fft → out
     let n_paths_a =
-        graph.paths("svr", "dac") * graph.paths("dac", "fft") * graph.paths("fft", "out");
+        graph.paths("svr", "dac")? * graph.paths("dac", "fft")? * graph.paths("fft", "out")?;
 
     // Count paths: svr → fft → dac → out
     let n_paths_b =
-        graph.paths("svr", "fft") * graph.paths("fft", "dac") * graph.paths("dac", "out");
+        graph.paths("svr", "fft")? * graph.paths("fft", "dac")? * graph.paths("dac", "out")?;
 
-    Some(n_paths_a + n_paths_b)
+    Ok(n_paths_a + n_paths_b)
+}
+
+/// Why a part has no answer.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Parse(ParseError),
+    /// A device the paths start or end at is not in the input.
+    MissingDevice(String),
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "{e}"),
+            Error::MissingDevice(name) => write!(f, "no device `{name}` in the input"),
+        }
+    }
 }
 
 /* === Input === */
@@ -37,16 +62,14 @@ struct Graph<'a> {
 }
 
 impl<'a> Graph<'a> {
-    fn parse(input: &'a str) -> Self {
+    fn parse(input: &'a str) -> Result<Self, ParseError> {
         let mut atoms = AtomTable::default();
         let mut edges = Vec::new();
         let mut indices = Vec::new();
 
-        for line in input.lines() {
-            let mut parts = line.split_ascii_whitespace();
-
-            let from_str = parts.next().unwrap();
-            let from = atoms.insert(&from_str[..3]);
+        for line in records(input, '\n') {
+            let (from, outputs) = split_pair(input, line, ':')?;
+            let from = atoms.insert(from);
 
             // If a node is encountered before it's definition,
             // the vector needs to be resized/padded.
@@ -58,7 +81,7 @@ impl<'a> Graph<'a> {
             let mut count = 0;
 
             // Dump connections into a flat edge list
-            for part in parts {
+            for part in outputs.split_ascii_whitespace() {
                 edges.push(atoms.insert(part));
                 count += 1;
             }
@@ -71,18 +94,18 @@ impl<'a> Graph<'a> {
             indices.resize(atoms.next_id as usize, (0, 0));
         }
 
-        Graph {
+        Ok(Graph {
             atoms,
             edges,
             indices,
-        }
+        })
     }
 
-    fn paths(&self, from: &str, to: &str) -> u64 {
-        let from = self.atoms.get(from);
-        let to = self.atoms.get(to);
+    fn paths(&self, from: &str, to: &str) -> Result<u64, Error> {
+        let from = self.atoms.get(from)?;
+        let to = self.atoms.get(to)?;
 
-        count_paths(
+        let n_paths = count_paths(
             from,
             |&n| unsafe {
                 let &(index, count) = self.indices.get_unchecked(n as usize);
@@ -93,7 +116,9 @@ impl<'a> Graph<'a> {
                     .copied()
             },
             |&n| n == to,
-        ) as u64
+        );
+
+        Ok(n_paths as u64)
     }
 }
 
@@ -106,8 +131,11 @@ struct AtomTable<'a> {
 }
 
 impl<'a> AtomTable<'a> {
-    fn get(&self, atom: &str) -> Id {
-        *self.table.get(atom).unwrap()
+    fn get(&self, atom: &str) -> Result<Id, Error> {
+        self.table
+            .get(atom)
+            .copied()
+            .ok_or_else(|| Error::MissingDevice(atom.into()))
     }
 
     fn insert(&mut self, atom: &'a str) -> Id {
@@ -131,8 +159,14 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(5));
+        assert_eq!(result, Ok(5));
     }
 
     // Part 2 uses a different example
+
+    #[test]
+    fn test_missing_device() {
+        let result = part_two("you: zzz\nsvr: out\n");
+        assert_eq!(result, Err(Error::MissingDevice("dac".into())));
+    }
 }
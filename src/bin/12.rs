@@ -1,10 +1,12 @@
+use advent_of_code::parse::{ParseError, number, records, sections, split_pair};
+
 advent_of_code::solution!(12);
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (shapes, regions) = parse_input(input);
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let (shapes, regions) = parse_input(input)?;
     let answer = regions.iter().filter(|r| can_fit(&shapes, r)).count();
 
-    Some(answer as u64)
+    Ok(answer as u64)
 }
 
 pub fn part_two(_input: &str) -> Option<u64> {
@@ -33,49 +35,55 @@ struct Region {
     counts: Box<[u32]>,
 }
 
-fn parse_input(input: &str) -> (Box<[u32]>, Box<[Region]>) {
+/// The area of each shape, and the regions to fill.
+type Presents = (Box<[u32]>, Box<[Region]>);
+
+fn parse_input(input: &str) -> Result<Presents, ParseError> {
     let mut shapes = Vec::new();
     let mut regions = Vec::new();
-    let mut current_shape_area = 0;
 
-    for line in input.lines() {
-        if line.is_empty() {
-            continue;
-        }
+    for section in sections(input) {
+        let (header, rest) = section.split_once('\n').unwrap_or((section, ""));
 
-        if line.contains('x') {
-            // Region line: "WxH: c0 c1 c2 ..."
-            let (dims, counts_str) = line.split_once(": ").unwrap();
-            let (w, h) = dims.split_once('x').unwrap();
-
-            let counts = counts_str
-                .split_ascii_whitespace()
-                .map(|s| s.parse().unwrap())
-                .collect::<Vec<_>>()
-                .into_boxed_slice();
-
-            regions.push(Region {
-                width: w.parse().unwrap(),
-                height: h.parse().unwrap(),
-                counts,
-            });
-        } else if line.ends_with(':') {
-            // Shape header "N:" - save previous shape if any
-            if current_shape_area > 0 {
-                shapes.push(current_shape_area);
-            }
-
-            current_shape_area = 0;
+        if header.ends_with(':') {
+            // Shape "N:" followed by its rows - count '#' characters
+            shapes.push(rest.bytes().filter(|&b| b == b'#').count() as u32);
         } else {
-            // Shape row - count '#' characters
-            current_shape_area += line.bytes().filter(|&b| b == b'#').count() as u32;
+            for line in records(section, '\n') {
+                regions.push(parse_region(input, line)?);
+            }
         }
     }
 
-    // Save last shape
-    if current_shape_area > 0 {
-        shapes.push(current_shape_area);
-    }
+    Ok((shapes.into_boxed_slice(), regions.into_boxed_slice()))
+}
+
+/// Parses a region like `4x4: 0 0 0 0 2 0`.
+fn parse_region(input: &str, line: &str) -> Result<Region, ParseError> {
+    let (dims, counts) = split_pair(input, line, ':')?;
+    let (w, h) = split_pair(input, dims, 'x')?;
+
+    let counts = records(counts, ' ')
+        .map(|count| number(input, count))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_boxed_slice();
+
+    Ok(Region {
+        width: number(input, w)?,
+        height: number(input, h)?,
+        counts,
+    })
+}
 
-    (shapes.into_boxed_slice(), regions.into_boxed_slice())
+/* === Tests === */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_malformed_region() {
+        let error = part_one("0:\n###\n\n4x4: 1 a\n").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 8));
+    }
 }
@@ -0,0 +1,8 @@
+//! Runs the solutions of several days in-process, see [`advent_of_code::template::registry`].
+use advent_of_code::template::registry::{self, Solution};
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+fn main() {
+    registry::run(SOLUTIONS);
+}
@@ -0,0 +1,194 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+};
+
+/// Keeps the `capacity` items with the largest keys out of all items pushed into it, for
+/// top-K selection in `O(n log K)` time and `O(K)` space.
+///
+/// Wrap the keys in [`Reverse`] to keep the items with the smallest keys instead.
+///
+/// ```
+/// # use advent_of_code::ds::BoundedHeap;
+/// let mut heap = BoundedHeap::new(2);
+/// for (key, item) in [(3, 'a'), (1, 'b'), (4, 'c'), (2, 'd')] {
+///     heap.push(key, item);
+/// }
+/// assert_eq!(heap.into_sorted_vec(), [(4, 'c'), (3, 'a')]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BoundedHeap<T, K> {
+    /// A min-heap, so that the smallest key that is kept is at the top.
+    heap: BinaryHeap<Reverse<Entry<T, K>>>,
+    capacity: usize,
+}
+
+impl<T, K: Ord> BoundedHeap<T, K> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            heap: BinaryHeap::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns `true` once the heap holds `capacity` items, from when on a new item is only
+    /// kept if its key is larger than [`BoundedHeap::min_key`].
+    pub fn is_full(&self) -> bool {
+        self.heap.len() >= self.capacity
+    }
+
+    /// The smallest key that is kept.
+    pub fn min_key(&self) -> Option<&K> {
+        self.heap.peek().map(|Reverse(entry)| &entry.key)
+    }
+
+    /// Returns `true` if an item with `key` would be kept by [`BoundedHeap::push`]. This is
+    /// cheaper than building an item that is then rejected, e.g. in hot loops over candidates.
+    #[inline]
+    pub fn accepts(&self, key: &K) -> bool {
+        match self.min_key() {
+            Some(min_key) if self.is_full() => key > min_key,
+            _ => self.capacity > 0,
+        }
+    }
+
+    /// Offers an item to the heap. Returns `true` if it was kept, which evicts the item with
+    /// the smallest key once the heap is full.
+    #[inline]
+    pub fn push(&mut self, key: K, item: T) -> bool {
+        if !self.is_full() {
+            self.heap.push(Reverse(Entry { key, item }));
+            return true;
+        }
+
+        match self.heap.peek_mut() {
+            Some(mut smallest) if key > smallest.0.key => {
+                // Replacing the top restores the heap order once, instead of a pop and a push
+                *smallest = Reverse(Entry { key, item });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the kept keys and items, in no particular order.
+    pub fn into_vec(self) -> Vec<(K, T)> {
+        self.heap
+            .into_vec()
+            .into_iter()
+            .map(|Reverse(entry)| (entry.key, entry.item))
+            .collect()
+    }
+
+    /// Returns the kept keys and items, sorted by descending key.
+    pub fn into_sorted_vec(self) -> Vec<(K, T)> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(entry)| (entry.key, entry.item))
+            .collect()
+    }
+}
+
+/// An item ordered by its key alone, so that items need not be comparable.
+#[derive(Debug, Clone)]
+struct Entry<T, K> {
+    key: K,
+    item: T,
+}
+
+impl<T, K: Ord> Ord for Entry<T, K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+impl<T, K: Ord> PartialOrd for Entry<T, K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T, K: Ord> PartialEq for Entry<T, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<T, K: Ord> Eq for Entry<T, K> {}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::cmp::Reverse;
+
+    use super::BoundedHeap;
+    use crate::rng::Rng;
+
+    #[test]
+    fn keeps_the_largest_keys() {
+        let mut heap = BoundedHeap::new(3);
+        for (key, item) in [(5, 'a'), (1, 'b'), (7, 'c'), (3, 'd'), (6, 'e')] {
+            heap.push(key, item);
+        }
+
+        assert!(heap.is_full());
+        assert_eq!(heap.min_key(), Some(&5));
+        assert!(!heap.accepts(&4));
+        assert!(heap.accepts(&6));
+        assert!(!heap.push(4, 'f'));
+        assert_eq!(heap.into_sorted_vec(), [(7, 'c'), (6, 'e'), (5, 'a')]);
+
+        let mut empty = BoundedHeap::new(0);
+        assert!(!empty.accepts(&1));
+        assert!(!empty.push(1, ()));
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn keeps_the_smallest_reversed_keys() {
+        let mut heap = BoundedHeap::new(2);
+        for key in [5, 1, 7, 3] {
+            heap.push(Reverse(key), key);
+        }
+
+        let items: Vec<i32> = heap.into_sorted_vec().into_iter().map(|(_, k)| k).collect();
+        assert_eq!(items, [1, 3]);
+    }
+
+    /// Compares random streams against sorting all of their keys.
+    #[test]
+    fn agrees_with_sorting() {
+        for seed in 0..100 {
+            let mut rng = Rng::new(seed);
+            let capacity = rng.index(20);
+            let keys: Vec<u64> = (0..rng.index(100)).map(|_| rng.range(0..=50)).collect();
+
+            let mut heap = BoundedHeap::new(capacity);
+            for (i, &key) in keys.iter().enumerate() {
+                heap.push(key, i);
+            }
+
+            let mut expected = keys.clone();
+            expected.sort_unstable_by(|a, b| b.cmp(a));
+            expected.truncate(capacity);
+
+            let kept = heap.into_sorted_vec();
+            assert_eq!(kept.iter().map(|&(k, _)| k).collect::<Vec<_>>(), expected);
+            assert!(kept.iter().all(|&(k, i)| keys[i] == k));
+        }
+    }
+}
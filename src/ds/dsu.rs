@@ -0,0 +1,149 @@
+/// A Disjoint Set Union (Union-Find) over the elements `0..len`.
+///
+/// Unions attach the smaller component to the larger one and lookups halve the paths they
+/// walk, so that any sequence of operations runs in nearly linear time.
+#[derive(Debug, Clone)]
+pub struct DisjointSetUnion {
+    parents: Vec<usize>,
+    /// The size of each component, only valid at its root.
+    sizes: Vec<usize>,
+    num_sets: usize,
+}
+
+impl DisjointSetUnion {
+    /// Creates a Disjoint Set Union with `len` elements, each in its own set.
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            num_sets: len,
+        }
+    }
+
+    /// The number of elements.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// The number of disjoint sets.
+    pub fn num_sets(&self) -> usize {
+        self.num_sets
+    }
+
+    /// Finds the root of the set containing element `i`.
+    pub fn find(&mut self, mut i: usize) -> usize {
+        while i != self.parents[i] {
+            self.parents[i] = self.parents[self.parents[i]];
+            i = self.parents[i];
+        }
+
+        i
+    }
+
+    /// Unites the sets containing elements `i` and `j`.
+    /// Returns `true` if the sets were separate and have been united, `false` otherwise.
+    pub fn union(&mut self, i: usize, j: usize) -> bool {
+        let (mut root_i, mut root_j) = (self.find(i), self.find(j));
+
+        if root_i == root_j {
+            return false;
+        }
+
+        if self.sizes[root_i] > self.sizes[root_j] {
+            (root_i, root_j) = (root_j, root_i);
+        }
+
+        self.parents[root_i] = root_j;
+        self.sizes[root_j] += self.sizes[root_i];
+        self.num_sets -= 1;
+
+        true
+    }
+
+    /// Returns `true` if elements `i` and `j` are in the same set.
+    pub fn connected(&mut self, i: usize, j: usize) -> bool {
+        self.find(i) == self.find(j)
+    }
+
+    /// Returns the size of the set containing element `i`.
+    pub fn size(&mut self, i: usize) -> usize {
+        let root = self.find(i);
+        self.sizes[root]
+    }
+
+    /// Returns the sizes of all sets, in no particular order.
+    pub fn component_sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.parents
+            .iter()
+            .zip(&self.sizes)
+            .enumerate()
+            .filter(|&(i, (&parent, _))| i == parent)
+            .map(|(_, (_, &size))| size)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::DisjointSetUnion;
+    use crate::rng::Rng;
+
+    #[test]
+    fn tracks_components_and_sizes() {
+        let mut dsu = DisjointSetUnion::new(5);
+        assert!(dsu.union(0, 1));
+        assert!(dsu.union(3, 1));
+        assert!(!dsu.union(0, 3));
+
+        assert_eq!(dsu.num_sets(), 3);
+        assert_eq!(dsu.size(3), 3);
+        assert!(dsu.connected(0, 3));
+        assert!(!dsu.connected(0, 4));
+
+        let mut sizes: Vec<usize> = dsu.component_sizes().collect();
+        sizes.sort_unstable();
+        assert_eq!(sizes, [1, 1, 3]);
+    }
+
+    /// Compares random unions against a naive labelling of the components.
+    #[test]
+    fn agrees_with_naive_labels() {
+        for seed in 0..50 {
+            let mut rng = Rng::new(seed);
+            let len = 1 + rng.index(40);
+            let mut dsu = DisjointSetUnion::new(len);
+            let mut labels: Vec<usize> = (0..len).collect();
+
+            for _ in 0..rng.index(2 * len) {
+                let (i, j) = (rng.index(len), rng.index(len));
+                let (from, to) = (labels[i], labels[j]);
+
+                assert_eq!(dsu.union(i, j), from != to);
+                labels
+                    .iter_mut()
+                    .filter(|l| **l == from)
+                    .for_each(|l| *l = to);
+            }
+
+            for i in 0..len {
+                let size = labels.iter().filter(|&&l| l == labels[i]).count();
+                assert_eq!(dsu.size(i), size);
+
+                for j in 0..len {
+                    assert_eq!(dsu.connected(i, j), labels[i] == labels[j]);
+                }
+            }
+
+            let mut distinct = labels.clone();
+            distinct.sort_unstable();
+            distinct.dedup();
+            assert_eq!(dsu.num_sets(), distinct.len());
+            assert_eq!(dsu.component_sizes().sum::<usize>(), len);
+        }
+    }
+}
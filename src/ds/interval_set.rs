@@ -0,0 +1,160 @@
+use std::ops::RangeInclusive;
+
+/// A set of integers stored as sorted, disjoint inclusive ranges.
+///
+/// Inserted ranges are merged with the ranges they overlap or touch, so that `1..=3` and
+/// `4..=6` become `1..=6`. Lookups are binary searches over the ranges.
+///
+/// ```
+/// # use advent_of_code::ds::IntervalSet;
+/// let set: IntervalSet = [3..=5, 10..=14, 16..=20, 12..=18].into_iter().collect();
+/// assert_eq!(set.ranges(), [3..=5, 10..=20]);
+/// assert_eq!(set.total_len(), 14);
+/// assert!(set.contains(17) && !set.contains(8));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    ranges: Vec<RangeInclusive<u64>>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The disjoint ranges of the set, in ascending order.
+    pub fn ranges(&self) -> &[RangeInclusive<u64>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Adds all integers in `range` to the set. Empty ranges are ignored.
+    pub fn insert(&mut self, range: RangeInclusive<u64>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        // The ranges from `first` to `last` (exclusive) overlap or touch the new range
+        let first = self
+            .ranges
+            .partition_point(|r| r.end().saturating_add(1) < start);
+        let last =
+            first + self.ranges[first..].partition_point(|r| *r.start() <= end.saturating_add(1));
+
+        if first < last {
+            start = start.min(*self.ranges[first].start());
+            end = end.max(*self.ranges[last - 1].end());
+        }
+
+        self.ranges.splice(first..last, [start..=end]);
+    }
+
+    /// Adds all integers of `other` to the set.
+    pub fn merge(&mut self, other: &IntervalSet) {
+        for range in &other.ranges {
+            self.insert(range.clone());
+        }
+    }
+
+    pub fn contains(&self, value: u64) -> bool {
+        let i = self.ranges.partition_point(|r| *r.end() < value);
+        self.ranges.get(i).is_some_and(|r| r.contains(&value))
+    }
+
+    /// The number of integers in the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics on overflow, if the set contains all `u64` values.
+    pub fn total_len(&self) -> u64 {
+        self.ranges.iter().map(|r| r.end() - r.start() + 1).sum()
+    }
+}
+
+impl FromIterator<RangeInclusive<u64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<u64>>>(ranges: I) -> Self {
+        let mut set = Self::new();
+        set.extend(ranges);
+        set
+    }
+}
+
+impl Extend<RangeInclusive<u64>> for IntervalSet {
+    fn extend<I: IntoIterator<Item = RangeInclusive<u64>>>(&mut self, ranges: I) {
+        for range in ranges {
+            self.insert(range);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::IntervalSet;
+    use crate::rng::Rng;
+
+    #[test]
+    fn merges_overlapping_and_touching_ranges() {
+        let mut set: IntervalSet = [10..=12, 1..=3, 20..=25].into_iter().collect();
+        assert_eq!(set.ranges(), [1..=3, 10..=12, 20..=25]);
+
+        set.insert(4..=9);
+        assert_eq!(set.ranges(), [1..=12, 20..=25]);
+
+        set.insert(15..=30);
+        // NOTE: empty ranges are ignored.
+        #[allow(clippy::reversed_empty_ranges)]
+        set.insert(8..=2);
+        assert_eq!(set.ranges(), [1..=12, 15..=30]);
+        assert_eq!(set.total_len(), 28);
+
+        let mut other: IntervalSet = [13..=14, 40..=40].into_iter().collect();
+        other.merge(&set);
+        assert_eq!(other.ranges(), [1..=30, 40..=40]);
+    }
+
+    #[test]
+    fn handles_the_ends_of_u64() {
+        let set: IntervalSet = [0..=0, u64::MAX..=u64::MAX, 1..=5].into_iter().collect();
+        assert_eq!(set.ranges(), [0..=5, u64::MAX..=u64::MAX]);
+        assert!(set.contains(u64::MAX) && !set.contains(u64::MAX - 1));
+    }
+
+    /// Compares random insertions against a bitmap of the covered integers.
+    #[test]
+    fn agrees_with_a_bitmap() {
+        for seed in 0..100 {
+            let mut rng = Rng::new(seed);
+            let mut set = IntervalSet::new();
+            let mut covered = [false; 64];
+
+            for _ in 0..rng.index(12) {
+                let start = rng.range(0..=60);
+                let end = start + rng.range(0..=8).min(63 - start);
+
+                set.insert(start..=end);
+                covered[start as usize..=end as usize].fill(true);
+            }
+
+            for (value, &covered) in covered.iter().enumerate() {
+                assert_eq!(set.contains(value as u64), covered);
+            }
+            assert_eq!(
+                set.total_len(),
+                covered.iter().filter(|&&c| c).count() as u64
+            );
+
+            // The ranges are sorted, disjoint and separated by at least one integer
+            assert!(
+                set.ranges()
+                    .windows(2)
+                    .all(|pair| pair[0].end() + 1 < *pair[1].start())
+            );
+        }
+    }
+}
@@ -0,0 +1,12 @@
+//! Data structures shared by the solutions.
+//!
+//! - [`DisjointSetUnion`] merges elements into connected components (day 8).
+//! - [`BoundedHeap`] keeps the items with the largest keys of a stream (days 8 and 9).
+//! - [`IntervalSet`] merges integer ranges into disjoint ones (day 5).
+mod bounded_heap;
+mod dsu;
+mod interval_set;
+
+pub use bounded_heap::BoundedHeap;
+pub use dsu::DisjointSetUnion;
+pub use interval_set::IntervalSet;
@@ -0,0 +1,17 @@
+//! Dial rotations, one per line, e.g. `L68`.
+use std::fmt::Write;
+
+use crate::rng::Rng;
+
+const MAX_DISTANCE: u64 = 999;
+
+pub fn generate(rng: &mut Rng, rotations: usize) -> String {
+    let mut input = String::new();
+
+    for _ in 0..rotations {
+        let direction = if rng.chance(0.5) { 'L' } else { 'R' };
+        writeln!(input, "{direction}{}", rng.range(1..=MAX_DISTANCE)).unwrap();
+    }
+
+    input
+}
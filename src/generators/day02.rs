@@ -0,0 +1,31 @@
+//! Disjoint ranges of product IDs with up to ten digits, e.g. `11-22,95-115`.
+use std::collections::BTreeSet;
+
+use crate::rng::Rng;
+
+const MAX_DIGITS: u32 = 10;
+
+pub fn generate(rng: &mut Rng, ranges: usize) -> String {
+    // Picking the number of digits first spreads the ranges across all ID lengths.
+    let mut starts = BTreeSet::new();
+    while starts.len() < ranges {
+        let digits = rng.range(1..=u64::from(MAX_DIGITS)) as u32;
+        starts.insert(rng.range(10u64.pow(digits - 1)..=10u64.pow(digits) - 1));
+    }
+
+    let starts: Vec<u64> = starts.into_iter().collect();
+    let max_id = 10u64.pow(MAX_DIGITS) - 1;
+
+    let mut ranges: Vec<String> = starts
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            let limit = starts.get(i + 1).map_or(max_id, |next| next - 1);
+            let end = (start + rng.range(0..=start / 4 + 10)).min(limit);
+            format!("{start}-{end}")
+        })
+        .collect();
+    rng.shuffle(&mut ranges);
+
+    ranges.join(",") + "\n"
+}
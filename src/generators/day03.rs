@@ -0,0 +1,17 @@
+//! Banks of batteries with joltage ratings from 1 to 9, one bank per line.
+use crate::rng::Rng;
+
+const BANK_LENGTH: usize = 100;
+
+pub fn generate(rng: &mut Rng, banks: usize) -> String {
+    let mut input = String::with_capacity(banks * (BANK_LENGTH + 1));
+
+    for _ in 0..banks {
+        for _ in 0..BANK_LENGTH {
+            input.push(char::from(b'0' + rng.range(1..=9) as u8));
+        }
+        input.push('\n');
+    }
+
+    input
+}
@@ -0,0 +1,17 @@
+//! A square grid of paper rolls (`@`) and empty spaces (`.`).
+use crate::rng::Rng;
+
+const ROLL_DENSITY: f64 = 0.7;
+
+pub fn generate(rng: &mut Rng, side: usize) -> String {
+    let mut input = String::with_capacity(side * (side + 1));
+
+    for _ in 0..side {
+        for _ in 0..side {
+            input.push(if rng.chance(ROLL_DENSITY) { '@' } else { '.' });
+        }
+        input.push('\n');
+    }
+
+    input
+}
@@ -0,0 +1,38 @@
+//! Overlapping ranges of fresh ingredient IDs, a blank line and the available ingredient IDs.
+use std::fmt::Write;
+
+use crate::rng::Rng;
+
+const MAX_ID: u64 = 500_000_000_000_000;
+const MAX_WIDTH: u64 = 10_000_000_000_000;
+const IDS_PER_RANGE: usize = 5;
+
+pub fn generate(rng: &mut Rng, ids: usize) -> String {
+    let ranges: Vec<(u64, u64)> = (0..ids.div_ceil(IDS_PER_RANGE))
+        .map(|_| {
+            let start = rng.range(1..=MAX_ID - MAX_WIDTH);
+            (start, start + rng.range(0..=MAX_WIDTH))
+        })
+        .collect();
+
+    let mut input = String::new();
+
+    for (start, end) in &ranges {
+        writeln!(input, "{start}-{end}").unwrap();
+    }
+
+    input.push('\n');
+
+    // Half of the IDs are drawn from a range, so that both fresh and spoiled ones occur.
+    for _ in 0..ids {
+        let id = if rng.chance(0.5) {
+            let (start, end) = ranges[rng.index(ranges.len())];
+            rng.range(start..=end)
+        } else {
+            rng.range(1..=MAX_ID)
+        };
+        writeln!(input, "{id}").unwrap();
+    }
+
+    input
+}
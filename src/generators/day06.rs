@@ -0,0 +1,44 @@
+//! Cephalopod math homework: problems in columns of four numbers, with the operator below.
+//!
+//! The numbers of a problem are aligned either left or right, which matters when they are read
+//! column by column.
+use crate::rng::Rng;
+
+const ROWS: usize = 4;
+const MAX_DIGITS: u64 = 4;
+
+pub fn generate(rng: &mut Rng, problems: usize) -> String {
+    let mut lines = vec![String::new(); ROWS + 1];
+
+    for problem in 0..problems {
+        let numbers: Vec<String> = (0..ROWS)
+            .map(|_| {
+                let digits = rng.range(1..=MAX_DIGITS) as u32;
+                rng.range(10u64.pow(digits - 1)..=10u64.pow(digits) - 1)
+                    .to_string()
+            })
+            .collect();
+
+        let width = numbers.iter().map(String::len).max().unwrap();
+        let align_left = rng.chance(0.5);
+        let operator = if rng.chance(0.5) { "+" } else { "*" };
+
+        if problem > 0 {
+            for line in &mut lines {
+                line.push(' ');
+            }
+        }
+
+        for (line, number) in lines.iter_mut().zip(&numbers) {
+            if align_left {
+                line.push_str(&format!("{number:<width$}"));
+            } else {
+                line.push_str(&format!("{number:>width$}"));
+            }
+        }
+
+        lines[ROWS].push_str(&format!("{operator:<width$}"));
+    }
+
+    lines.join("\n") + "\n"
+}
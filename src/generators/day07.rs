@@ -0,0 +1,45 @@
+//! A tachyon manifold: the beam enters at `S` in the middle of the top row and hits splitters
+//! (`^`) placed on every other row, within the triangle the beam can reach. The first splitter
+//! is always present.
+//!
+//! The number of timelines grows exponentially with the size and exceeds `u64` for sizes above
+//! about 200 columns.
+use crate::rng::Rng;
+
+const SPLITTER_DENSITY: f64 = 0.5;
+
+pub fn generate(rng: &mut Rng, columns: usize) -> String {
+    // An odd width keeps the entry point centered, the extra row leaves the last one empty.
+    let width = columns.max(3) | 1;
+    let height = width + 1;
+    let center = width / 2;
+
+    let mut input = String::with_capacity(height * (width + 1));
+
+    for row in 0..height {
+        let level = row / 2;
+
+        for col in 0..width {
+            let offset = col.abs_diff(center);
+
+            let tile = if row == 0 && col == center {
+                'S'
+            } else if row > 0
+                && row % 2 == 0
+                && offset < level
+                && (level - 1 - offset) % 2 == 0
+                && (level == 1 || rng.chance(SPLITTER_DENSITY))
+            {
+                '^'
+            } else {
+                '.'
+            };
+
+            input.push(tile);
+        }
+
+        input.push('\n');
+    }
+
+    input
+}
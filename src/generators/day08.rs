@@ -0,0 +1,17 @@
+//! Positions of junction boxes in 3D space, one `x,y,z` per line.
+use std::fmt::Write;
+
+use crate::rng::Rng;
+
+const MAX_COORDINATE: u64 = 100_000;
+
+pub fn generate(rng: &mut Rng, boxes: usize) -> String {
+    let mut input = String::new();
+
+    for _ in 0..boxes {
+        let [x, y, z] = [(); 3].map(|()| rng.range(0..=MAX_COORDINATE));
+        writeln!(input, "{x},{y},{z}").unwrap();
+    }
+
+    input
+}
@@ -0,0 +1,64 @@
+//! The red tiles at the corners of a rectilinear polygon, in order, one `x,y` per line.
+//!
+//! The polygon is a band around a horizontal center line, whose upper and lower edges take small
+//! random steps from column to column, so that large rectangles fit inside like in the puzzle
+//! input. The number of red tiles is rounded up to a multiple of four, and is at most
+//! [`MAX_TILES`].
+use std::{collections::BTreeSet, fmt::Write};
+
+use crate::rng::Rng;
+
+const MAX_COORDINATE: u64 = 100_000;
+
+/// Every column of the band needs two distinct x coordinates of its own.
+pub const MAX_TILES: usize = 4 * MAX_COORDINATE as usize;
+
+pub fn generate(rng: &mut Rng, tiles: usize) -> String {
+    let columns = tiles.div_ceil(4).max(1);
+    let center = MAX_COORDINATE / 2;
+    // Smaller steps for more columns keep the edges equally smooth at any size.
+    let max_step = (MAX_COORDINATE / columns as u64).max(1);
+
+    let mut xs = BTreeSet::new();
+    while xs.len() < columns + 1 {
+        xs.insert(rng.range(0..=MAX_COORDINATE));
+    }
+    let xs: Vec<u64> = xs.into_iter().collect();
+
+    // Neighbouring columns differ in height, so that no three corners are collinear.
+    let mut walk = |start: u64, min: u64, max: u64| {
+        let mut heights: Vec<u64> = vec![start];
+        while heights.len() < columns {
+            let previous = *heights.last().unwrap();
+            let height = rng
+                .range(previous.saturating_sub(max_step)..=previous + max_step)
+                .clamp(min, max);
+            if height != previous {
+                heights.push(height);
+            }
+        }
+        heights
+    };
+    let tops = walk(center + center / 2, center + 1, MAX_COORDINATE);
+    let bottoms = walk(center / 2, 0, center - 1);
+
+    let mut corners = Vec::with_capacity(4 * columns);
+
+    for i in 0..columns {
+        corners.push((xs[i], tops[i]));
+        corners.push((xs[i + 1], tops[i]));
+    }
+
+    for i in (0..columns).rev() {
+        corners.push((xs[i + 1], bottoms[i]));
+        corners.push((xs[i], bottoms[i]));
+    }
+
+    let mut input = String::new();
+
+    for (x, y) in corners {
+        writeln!(input, "{x},{y}").unwrap();
+    }
+
+    input
+}
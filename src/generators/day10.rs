@@ -0,0 +1,72 @@
+//! Machine specifications: the indicator light diagram, the wiring of the buttons and the
+//! joltage requirements, e.g. `[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}`.
+//!
+//! The light diagram and joltages are derived from random button presses, so every machine
+//! can be configured.
+use std::{collections::BTreeSet, fmt::Write};
+
+use itertools::Itertools;
+
+use crate::rng::Rng;
+
+const MIN_LIGHTS: u64 = 4;
+const MAX_LIGHTS: u64 = 10;
+const MAX_PRESSES: u64 = 30;
+
+pub fn generate(rng: &mut Rng, machines: usize) -> String {
+    let mut input = String::new();
+
+    for _ in 0..machines {
+        writeln!(input, "{}", machine(rng)).unwrap();
+    }
+
+    input
+}
+
+fn machine(rng: &mut Rng) -> String {
+    let lights = rng.range(MIN_LIGHTS..=MAX_LIGHTS) as usize;
+    let n_buttons = rng.range(lights as u64 - 1..=lights as u64 + 3) as usize;
+
+    let mut buttons: BTreeSet<Vec<usize>> = BTreeSet::new();
+    while buttons.len() < n_buttons {
+        let wired = (0..lights).filter(|_| rng.chance(0.4)).collect::<Vec<_>>();
+        if !wired.is_empty() {
+            buttons.insert(wired);
+        }
+    }
+    let mut buttons: Vec<Vec<usize>> = buttons.into_iter().collect();
+    rng.shuffle(&mut buttons);
+
+    // Every light is wired to some button.
+    for light in 0..lights {
+        if !buttons.iter().any(|button| button.contains(&light)) {
+            let i = rng.index(buttons.len());
+            buttons[i].push(light);
+            buttons[i].sort_unstable();
+        }
+    }
+
+    let mut diagram = vec![false; lights];
+    let mut joltages = vec![0; lights];
+
+    for button in &buttons {
+        let toggled = rng.chance(0.5);
+        let presses = rng.range(0..=MAX_PRESSES);
+
+        for &light in button {
+            diagram[light] ^= toggled;
+            joltages[light] += presses;
+        }
+    }
+
+    let diagram: String = diagram
+        .iter()
+        .map(|&on| if on { '#' } else { '.' })
+        .collect();
+    let buttons = buttons
+        .iter()
+        .map(|button| format!("({})", button.iter().join(",")))
+        .join(" ");
+
+    format!("[{diagram}] {buttons} {{{}}}", joltages.iter().join(","))
+}
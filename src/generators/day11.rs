@@ -0,0 +1,93 @@
+//! A directed acyclic graph of devices, one `name: outputs...` line per device.
+//!
+//! The devices are arranged in [`LAYERS`] layers between the server `svr` and `out`, with `you`
+//! in the first layer, `fft` after a third and `dac` after two thirds of the layers. Each device
+//! feeds some devices of the next layer and is fed by at least one of the previous layer. A
+//! fixed depth keeps the number of paths in the range of the puzzle input at any size. Device
+//! names have three letters, which limits the size to [`MAX_DEVICES`].
+use std::collections::HashSet;
+
+use itertools::Itertools;
+
+use crate::rng::Rng;
+
+const LAYERS: usize = 32;
+const MAX_OUTPUTS: u64 = 4;
+const MIN_DEVICES: usize = 8;
+
+/// All three-letter names but the five fixed ones, plus `svr` and `out`, which are not counted
+/// in the layers.
+pub const MAX_DEVICES: usize = 26 * 26 * 26 - 5 + 2;
+
+pub fn generate(rng: &mut Rng, devices: usize) -> String {
+    let n = devices.max(MIN_DEVICES);
+    let layer_count = LAYERS.min(n - 2);
+
+    let mut used: HashSet<String> = ["svr", "you", "fft", "dac", "out"]
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+    let mut names: Vec<String> = Vec::with_capacity(n - 2);
+    while names.len() < n - 2 {
+        let name: String = (0..3)
+            .map(|_| char::from(b'a' + rng.range(0..=25) as u8))
+            .collect();
+        if used.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+
+    let mut layers: Vec<Vec<String>> = vec![vec!["svr".into()]];
+    let mut names = names.into_iter();
+    for i in 0..layer_count {
+        let size = (n - 2) / layer_count + usize::from(i < (n - 2) % layer_count);
+        layers.push(names.by_ref().take(size).collect());
+    }
+    layers.push(vec!["out".into()]);
+
+    for (layer, name) in [
+        (1, "you"),
+        (layer_count / 3, "fft"),
+        (2 * layer_count / 3, "dac"),
+    ] {
+        let i = rng.index(layers[layer].len());
+        layers[layer][i] = name.into();
+    }
+
+    let mut lines: Vec<String> = Vec::with_capacity(n - 1);
+
+    for (layer, next) in layers.iter().tuple_windows() {
+        let mut outputs: Vec<Vec<usize>> = layer
+            .iter()
+            .map(|_| {
+                let count = rng.range(1..=MAX_OUTPUTS).min(next.len() as u64) as usize;
+                let mut outputs: Vec<usize> = Vec::with_capacity(count);
+                while outputs.len() < count {
+                    let output = rng.index(next.len());
+                    if !outputs.contains(&output) {
+                        outputs.push(output);
+                    }
+                }
+                outputs
+            })
+            .collect();
+
+        // Every device of the next layer gets an input, so that all devices are reachable.
+        for output in 0..next.len() {
+            if !outputs.iter().flatten().any(|&o| o == output) {
+                let i = rng.index(outputs.len());
+                outputs[i].push(output);
+            }
+        }
+
+        for (name, outputs) in layer.iter().zip(outputs) {
+            let outputs = outputs.iter().map(|&output| &next[output]).join(" ");
+            lines.push(format!("{name}: {outputs}"));
+        }
+    }
+
+    rng.shuffle(&mut lines);
+
+    lines.join("\n") + "\n"
+}
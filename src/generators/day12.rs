@@ -0,0 +1,63 @@
+//! Six present shapes within 3x3 cells, followed by regions under the trees with the number of
+//! presents of each shape, e.g. `12x5: 1 0 1 0 2 2`.
+//!
+//! Half of the regions are packed loosely, the other half get more presents than they have room
+//! for.
+use std::fmt::Write;
+
+use itertools::Itertools;
+
+use crate::rng::Rng;
+
+const SHAPES: usize = 6;
+const MIN_REGION_SIDE: u64 = 35;
+const MAX_REGION_SIDE: u64 = 50;
+
+pub fn generate(rng: &mut Rng, regions: usize) -> String {
+    let mut input = String::new();
+    let mut areas = Vec::with_capacity(SHAPES);
+
+    for i in 0..SHAPES {
+        let cells = rng.range(5..=7) as usize;
+        let mut shape = [true; 9];
+
+        // The center stays filled, so that the shape holds together.
+        while shape.iter().filter(|&&cell| cell).count() > cells {
+            let cell = rng.index(9);
+            if cell != 4 {
+                shape[cell] = false;
+            }
+        }
+
+        areas.push(cells as u64);
+        writeln!(input, "{i}:").unwrap();
+        for row in shape.chunks(3) {
+            let row: String = row
+                .iter()
+                .map(|&cell| if cell { '#' } else { '.' })
+                .collect();
+            writeln!(input, "{row}").unwrap();
+        }
+        input.push('\n');
+    }
+
+    for _ in 0..regions {
+        let width = rng.range(MIN_REGION_SIDE..=MAX_REGION_SIDE);
+        let height = rng.range(MIN_REGION_SIDE..=MAX_REGION_SIDE);
+
+        let fill = if rng.chance(0.5) { 0.7 } else { 1.1 };
+        let target = ((width * height) as f64 * fill) as u64;
+
+        let mut counts = [0u64; SHAPES];
+        let mut area = 0;
+        while area < target {
+            let shape = rng.index(SHAPES);
+            counts[shape] += 1;
+            area += areas[shape];
+        }
+
+        writeln!(input, "{width}x{height}: {}", counts.iter().join(" ")).unwrap();
+    }
+
+    input
+}
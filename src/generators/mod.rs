@@ -0,0 +1,180 @@
+//! Random puzzle inputs of the 2025 event, for stress tests and scaling benchmarks.
+//!
+//! Every generator takes a size (e.g. the number of rotations for day 1) and a seed. The same
+//! size and seed always produce the same input, see `cargo gen`.
+use crate::{day, rng::Rng, template::Day};
+
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day07;
+mod day08;
+mod day09;
+mod day10;
+mod day11;
+mod day12;
+
+pub struct Generator {
+    pub day: Day,
+    /// What the size counts, e.g. `rotations`.
+    pub unit: &'static str,
+    /// A size similar to the one of the puzzle input.
+    pub default_size: usize,
+    /// The largest size the generator can produce, if it is limited.
+    pub max_size: Option<usize>,
+    generate: fn(&mut Rng, usize) -> String,
+}
+
+impl Generator {
+    /// Generates an input of a size that passed [`check_size`](Self::check_size).
+    pub fn generate(&self, size: usize, seed: u64) -> String {
+        (self.generate)(&mut Rng::new(seed), size)
+    }
+
+    /// Checks that the generator can produce an input of the given size.
+    pub fn check_size(&self, size: usize) -> Result<(), String> {
+        match self.max_size {
+            Some(max_size) if size > max_size => Err(format!(
+                "the generator of day {} supports at most {max_size} {}, got {size}.",
+                self.day, self.unit
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+pub const GENERATORS: &[Generator] = &[
+    Generator {
+        day: day!(1, year = 2025),
+        unit: "rotations",
+        default_size: 4500,
+        max_size: None,
+        generate: day01::generate,
+    },
+    Generator {
+        day: day!(2, year = 2025),
+        unit: "ranges",
+        default_size: 35,
+        max_size: None,
+        generate: day02::generate,
+    },
+    Generator {
+        day: day!(3, year = 2025),
+        unit: "battery banks",
+        default_size: 200,
+        max_size: None,
+        generate: day03::generate,
+    },
+    Generator {
+        day: day!(4, year = 2025),
+        unit: "columns and rows",
+        default_size: 137,
+        max_size: None,
+        generate: day04::generate,
+    },
+    Generator {
+        day: day!(5, year = 2025),
+        unit: "ingredient IDs",
+        default_size: 1000,
+        max_size: None,
+        generate: day05::generate,
+    },
+    Generator {
+        day: day!(6, year = 2025),
+        unit: "problems",
+        default_size: 1000,
+        max_size: None,
+        generate: day06::generate,
+    },
+    Generator {
+        day: day!(7, year = 2025),
+        unit: "columns",
+        default_size: 141,
+        max_size: None,
+        generate: day07::generate,
+    },
+    Generator {
+        day: day!(8, year = 2025),
+        unit: "junction boxes",
+        default_size: 1000,
+        max_size: None,
+        generate: day08::generate,
+    },
+    Generator {
+        day: day!(9, year = 2025),
+        unit: "red tiles",
+        default_size: 496,
+        max_size: Some(day09::MAX_TILES),
+        generate: day09::generate,
+    },
+    Generator {
+        day: day!(10, year = 2025),
+        unit: "machines",
+        default_size: 180,
+        max_size: None,
+        generate: day10::generate,
+    },
+    Generator {
+        day: day!(11, year = 2025),
+        unit: "devices",
+        default_size: 600,
+        max_size: Some(day11::MAX_DEVICES),
+        generate: day11::generate,
+    },
+    Generator {
+        day: day!(12, year = 2025),
+        unit: "regions",
+        default_size: 1000,
+        max_size: None,
+        generate: day12::generate,
+    },
+];
+
+/// Returns the generator of a day, if it has one.
+pub fn find(day: Day) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|generator| generator.day == day)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::GENERATORS;
+
+    #[test]
+    fn generates_deterministic_inputs() {
+        for generator in GENERATORS {
+            let input = generator.generate(20, 7);
+            assert!(!input.is_empty(), "day {}", generator.day);
+            assert!(input.ends_with('\n'), "day {}", generator.day);
+            assert_eq!(input, generator.generate(20, 7), "day {}", generator.day);
+            assert_ne!(input, generator.generate(20, 8), "day {}", generator.day);
+        }
+    }
+
+    #[test]
+    fn generates_inputs_at_the_limit() {
+        for generator in GENERATORS {
+            let Some(max_size) = generator.max_size else {
+                continue;
+            };
+
+            assert!(
+                generator.check_size(max_size).is_ok(),
+                "day {}",
+                generator.day
+            );
+            assert!(
+                generator.check_size(max_size + 1).is_err(),
+                "day {}",
+                generator.day
+            );
+
+            let input = generator.generate(max_size, 7);
+            assert!(input.ends_with('\n'), "day {}", generator.day);
+        }
+    }
+}
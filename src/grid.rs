@@ -0,0 +1,452 @@
+//! A two-dimensional grid, for puzzles whose input is a grid of characters.
+//!
+//! The cells are stored row by row in a single vector. A grid can be surrounded by a border of
+//! padding cells, so that the neighbours of every cell inside it can be visited with fixed
+//! stride offsets and without bounds checks.
+//!
+//! Puzzles that only read the grid can borrow it from the input with a [`GridView`] instead.
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+use crate::parse::{ParseError, grid_size};
+
+/// The column and row steps to the orthogonal neighbours: up, left, right, down.
+const DIRECTIONS4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// The column and row steps to all eight neighbours, row by row.
+const DIRECTIONS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+    padding: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid of `width` × `height` cells, all set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self::with_padding(width, height, 0, value)
+    }
+
+    /// Creates a grid of `width` × `height` cells surrounded by `padding` cells on every side,
+    /// all set to `value`.
+    pub fn with_padding(width: usize, height: usize, padding: usize, value: T) -> Self {
+        let cells = vec![value; (width + 2 * padding) * (height + 2 * padding)];
+
+        Self {
+            cells,
+            width,
+            height,
+            padding,
+        }
+    }
+
+    /// Parses a grid with one row per line, converting each byte with `cell`.
+    ///
+    /// ```
+    /// # use advent_of_code::grid::Grid;
+    /// let grid = Grid::parse("..@\n@@.\n", |b| b == b'@').unwrap();
+    /// assert_eq!((grid.width(), grid.height()), (3, 2));
+    /// assert_eq!(grid[(2, 0)], true);
+    /// ```
+    pub fn parse(input: &str, cell: impl FnMut(u8) -> T) -> Result<Self, ParseError>
+    where
+        T: Default,
+    {
+        Self::parse_padded(input, 0, T::default(), cell)
+    }
+
+    /// Parses a grid like [`Grid::parse`], surrounded by `padding` cells set to `border`.
+    pub fn parse_padded(
+        input: &str,
+        padding: usize,
+        border: T,
+        mut cell: impl FnMut(u8) -> T,
+    ) -> Result<Self, ParseError> {
+        let (width, height) = grid_size(input)?;
+        let mut grid = Self::with_padding(width, height, padding, border);
+
+        for (y, row) in input.lines().enumerate() {
+            let start = grid.index(0, y);
+            for (target, byte) in grid.cells[start..start + width].iter_mut().zip(row.bytes()) {
+                *target = cell(byte);
+            }
+        }
+
+        Ok(grid)
+    }
+}
+
+impl<T> Grid<T> {
+    /// The number of columns, without padding.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of rows, without padding.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The distance between vertically adjacent cells in the underlying vector.
+    pub fn stride(&self) -> usize {
+        self.width + 2 * self.padding
+    }
+
+    /// Returns the index of the cell in column `x` and row `y`, which must be in the grid.
+    pub fn index(&self, x: usize, y: usize) -> usize {
+        debug_assert!(x < self.width && y < self.height);
+        (y + self.padding) * self.stride() + x + self.padding
+    }
+
+    /// Returns the column and row of the cell at `index`, which must not be a padding cell.
+    pub fn position(&self, index: usize) -> (usize, usize) {
+        let stride = self.stride();
+        (index % stride - self.padding, index / stride - self.padding)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[self.index(x, y)])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            let index = self.index(x, y);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cell at `index`, including padding cells, without bounds checks.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be less than the number of cells, including padding.
+    pub unsafe fn get_unchecked(&self, index: usize) -> &T {
+        // SAFETY: guaranteed by the caller.
+        unsafe { self.cells.get_unchecked(index) }
+    }
+
+    /// Returns the cell at `index`, including padding cells, without bounds checks.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be less than the number of cells, including padding.
+    pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
+        // SAFETY: guaranteed by the caller.
+        unsafe { self.cells.get_unchecked_mut(index) }
+    }
+
+    /// Returns the indices of all cells inside the padding, row by row.
+    pub fn indices(&self) -> impl Iterator<Item = usize> + use<T> {
+        let (width, height, padding, stride) =
+            (self.width, self.height, self.padding, self.stride());
+        (padding..padding + height)
+            .flat_map(move |y| y * stride + padding..y * stride + padding + width)
+    }
+
+    /// Returns the cells of row `y`, without padding.
+    pub fn row(&self, y: usize) -> &[T] {
+        let start = self.index(0, y);
+        &self.cells[start..start + self.width]
+    }
+
+    /// Returns all rows from top to bottom, without padding.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// Returns the cells of column `x` from top to bottom, without padding.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells[self.index(x, 0)..]
+            .iter()
+            .step_by(self.stride())
+            .take(self.height)
+    }
+
+    /// Returns the index offsets of the four orthogonal neighbours: up, left, right, down.
+    pub fn offsets4(&self) -> [isize; 4] {
+        let stride = self.stride() as isize;
+        DIRECTIONS4.map(|(dx, dy)| dy * stride + dx)
+    }
+
+    /// Returns the index offsets of all eight neighbours, row by row.
+    ///
+    /// Adding them to the index of a cell is only valid if the grid has padding, as neighbours
+    /// beyond the left and right edges would otherwise wrap around to the adjacent rows.
+    pub fn offsets8(&self) -> [isize; 8] {
+        let stride = self.stride() as isize;
+        DIRECTIONS8.map(|(dx, dy)| dy * stride + dx)
+    }
+
+    /// Returns the indices of the orthogonal neighbours of the cell at `index`, in the order of
+    /// [`Grid::offsets4`]. Neighbours outside the grid are skipped, unless they are padding.
+    pub fn neighbours4(&self, index: usize) -> impl Iterator<Item = usize> + use<T> {
+        self.neighbours(index, DIRECTIONS4)
+    }
+
+    /// Returns the indices of all eight neighbours of the cell at `index`, in the order of
+    /// [`Grid::offsets8`]. Neighbours outside the grid are skipped, unless they are padding.
+    pub fn neighbours8(&self, index: usize) -> impl Iterator<Item = usize> + use<T> {
+        self.neighbours(index, DIRECTIONS8)
+    }
+
+    fn neighbours<const N: usize>(
+        &self,
+        index: usize,
+        directions: [(isize, isize); N],
+    ) -> impl Iterator<Item = usize> + use<T, N> {
+        let stride = self.stride();
+        let rows = self.height + 2 * self.padding;
+        let (x, y) = (index % stride, index / stride);
+
+        directions.into_iter().filter_map(move |(dx, dy)| {
+            let nx = x.checked_add_signed(dx).filter(|&nx| nx < stride)?;
+            let ny = y.checked_add_signed(dy).filter(|&ny| ny < rows)?;
+            Some(ny * stride + nx)
+        })
+    }
+
+    /// Renders the grid without padding, one row per line.
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        let mut rendered = String::with_capacity((self.width + 1) * self.height);
+
+        for row in self.rows() {
+            rendered.extend(row.iter().map(&mut cell));
+            rendered.push('\n');
+        }
+
+        rendered
+    }
+}
+
+impl<T> Index<usize> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.cells[index]
+    }
+}
+
+impl<T> IndexMut<usize> for Grid<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.cells[index]
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y).expect("position out of the grid")
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y).expect("position out of the grid")
+    }
+}
+
+/// Prints the grid without padding, one row per line.
+impl<T: Copy + Into<char>> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(|&cell| cell.into()))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A rectangular grid of bytes borrowed from the input, with one row per line.
+///
+/// ```
+/// # use advent_of_code::grid::GridView;
+/// let grid = GridView::new("..@\n@@.\n").unwrap();
+/// assert_eq!((grid.width(), grid.height()), (3, 2));
+/// assert_eq!(grid.row(1), b"@@.");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridView<'a> {
+    input: &'a str,
+    width: usize,
+    height: usize,
+    /// The distance between the starts of two rows, including the line ending.
+    stride: usize,
+}
+
+impl<'a> GridView<'a> {
+    /// Borrows the grid in `input`, rejecting rows of different lengths.
+    pub fn new(input: &'a str) -> Result<Self, ParseError> {
+        let (width, height) = grid_size(input)?;
+        let stride = input.find('\n').map_or(input.len(), |end| end + 1);
+
+        Ok(Self {
+            input,
+            width,
+            height,
+            stride,
+        })
+    }
+
+    /// Returns the number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the bytes of row `y`.
+    ///
+    /// NOTE: rows are located by their offset, so every line must end the same way.
+    pub fn row(&self, y: usize) -> &'a [u8] {
+        let start = y * self.stride;
+        &self.input.as_bytes()[start..start + self.width]
+    }
+
+    /// Returns all rows from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &'a [u8]> + use<'a> {
+        self.input.lines().map(str::as_bytes)
+    }
+
+    /// Counts the cells equal to `cell` in the 3×3 neighbourhood of every cell of row `y`,
+    /// the cell itself included. The counts slide along the row, so every cell of the rows
+    /// above and below is read once.
+    ///
+    /// ```
+    /// # use advent_of_code::grid::GridView;
+    /// let grid = GridView::new("@.@\n.@@\n").unwrap();
+    /// assert_eq!(grid.counts3x3(0, b'@').collect::<Vec<_>>(), [2, 4, 3]);
+    /// ```
+    pub fn counts3x3(&self, y: usize, cell: u8) -> impl Iterator<Item = u8> + use<'a> {
+        let row = |y: Option<usize>| {
+            y.filter(|&y| y < self.height)
+                .map_or(&[][..], |y| self.row(y))
+        };
+        let window = [row(y.checked_sub(1)), self.row(y), row(Some(y + 1))];
+        let column = move |x: usize| -> u8 {
+            window
+                .iter()
+                .map(|row| row.get(x).map_or(0, |&b| u8::from(b == cell)))
+                .sum()
+        };
+
+        let (mut left, mut center) = (0, column(0));
+
+        (0..self.width).map(move |x| {
+            let right = column(x + 1);
+            let count = left + center + right;
+            (left, center) = (center, right);
+            count
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Grid, GridView};
+
+    const INPUT: &str = "ab.\n.cd\nef.\n";
+
+    #[test]
+    fn parses_and_prints_grids() {
+        let grid = Grid::parse(INPUT, |b| b).unwrap();
+        assert_eq!((grid.width(), grid.height(), grid.stride()), (3, 3, 3));
+        assert_eq!(grid[(1, 1)], b'c');
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.to_string(), INPUT);
+
+        let padded = Grid::parse_padded(INPUT, 2, b'#', |b| b).unwrap();
+        assert_eq!(padded.stride(), 7);
+        assert_eq!(padded[(1, 1)], b'c');
+        assert_eq!(padded.to_string(), INPUT);
+        assert!(Grid::parse("ab\nc\n", |b| b).is_err());
+    }
+
+    #[test]
+    fn views_rows_and_columns() {
+        let grid = Grid::parse_padded(INPUT, 1, b'#', |b| b).unwrap();
+        assert_eq!(grid.row(1), b".cd");
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), b".d.");
+        assert_eq!(grid.rows().count(), 3);
+
+        let cells: Vec<u8> = grid.indices().map(|i| grid[i]).collect();
+        assert_eq!(cells, b"ab..cdef.");
+        assert_eq!(grid.position(grid.index(2, 1)), (2, 1));
+    }
+
+    #[test]
+    fn borrows_grids() {
+        let grid = GridView::new(INPUT).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.row(2), b"ef.");
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [b"ab.", b".cd", b"ef."]);
+
+        let counts: Vec<u8> = grid.counts3x3(1, b'.').collect();
+        assert_eq!(counts, [1, 3, 2]);
+
+        let crlf = GridView::new("ab\r\ncd\r\n").unwrap();
+        assert_eq!(crlf.row(1), b"cd");
+        assert!(GridView::new("ab\nc\n").is_err());
+    }
+
+    #[test]
+    fn visits_neighbours_with_padding() {
+        let grid = Grid::parse_padded(INPUT, 1, b'#', |b| b).unwrap();
+        let neighbours = |i| {
+            grid.neighbours8(i)
+                .map(|n| grid[n] as char)
+                .collect::<String>()
+        };
+
+        assert_eq!(neighbours(grid.index(0, 0)), "####b#.c");
+        assert_eq!(neighbours(grid.index(1, 1)), "ab..def.");
+        assert_eq!(
+            grid.neighbours4(grid.index(1, 1))
+                .map(|n| grid[n] as char)
+                .collect::<String>(),
+            "b.df"
+        );
+    }
+
+    #[test]
+    fn skips_neighbours_outside_the_grid() {
+        let grid = Grid::parse(INPUT, |b| b).unwrap();
+        let neighbours = |i| {
+            grid.neighbours8(i)
+                .map(|n| grid[n] as char)
+                .collect::<String>()
+        };
+
+        assert_eq!(neighbours(grid.index(0, 0)), "b.c");
+        assert_eq!(neighbours(grid.index(2, 1)), "b.cf.");
+        assert_eq!(neighbours(grid.index(2, 2)), "cdf");
+    }
+
+    #[test]
+    fn renders_custom_cells() {
+        let grid = Grid::parse(INPUT, |b| b.is_ascii_alphabetic()).unwrap();
+        assert_eq!(
+            grid.render(|&c| if c { '#' } else { '.' }),
+            "##.\n.##\n##.\n"
+        );
+    }
+}
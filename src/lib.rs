@@ -1,3 +1,8 @@
+pub mod ds;
+pub mod generators;
+pub mod grid;
+pub mod parse;
+pub mod rng;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
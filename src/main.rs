@@ -1,14 +1,19 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, generate, read, scaffold, solve, time, verify,
+};
 use args::{AppArguments, parse};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{Day, Event};
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        Day, Event, commands::time::TimeOptions, layout, limits::Limits, run_multi::Mode,
+        runner::BenchConfig, scaling::ScaleConfig,
+    };
+    use std::{process, time::Duration};
 
     pub enum AppArguments {
         Download {
@@ -24,17 +29,38 @@ mod args {
         },
         Solve {
             day: Day,
+            variant: Option<String>,
             release: bool,
             dhat: bool,
+            example: bool,
             submit: Option<u8>,
         },
         All {
+            event: Event,
             release: bool,
+            mode: Mode,
+            limits: Limits,
         },
         Time {
-            all: bool,
+            event: Event,
+            options: TimeOptions,
+        },
+        Scale {
+            day: Day,
+            config: ScaleConfig,
+            bench: BenchConfig,
+        },
+        Verify {
+            event: Event,
             day: Option<Day>,
-            store: bool,
+            release: bool,
+            mode: Mode,
+            limits: Limits,
+        },
+        Gen {
+            day: Day,
+            size: Option<usize>,
+            seed: u64,
         },
         #[cfg(feature = "today")]
         Today,
@@ -43,36 +69,132 @@ mod args {
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+
+        // days belong to the current event unless another one is selected with `--year`.
+        let event = args
+            .opt_value_from_str("--year")?
+            .unwrap_or_else(Event::current);
+        let parse_day = |s: String| Day::parse_in(event, &s);
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
+                event,
                 release: args.contains("--release"),
+                mode: parse_mode(&mut args)?,
+                limits: parse_limits(&mut args)?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let compare = args.contains("--compare");
+                let export = args.opt_value_from_str("--export")?;
+
+                if args.opt_value_from_str::<_, String>("--jobs")?.is_some() {
+                    eprintln!(
+                        "`--jobs` is not supported by `time`, parallel runs distort timings."
+                    );
+                    process::exit(1);
+                }
 
-                AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
+                let mode = if args.contains("--isolated") {
+                    Mode::Isolated
+                } else {
+                    Mode::InProcess
+                };
+                let bench = args
+                    .opt_value_from_str("--bench-time")?
+                    .map_or_else(BenchConfig::default, |ms| {
+                        BenchConfig::with_target_time(Duration::from_millis(ms))
+                    });
+                let bench = BenchConfig {
+                    warm_up: args
+                        .opt_value_from_str("--warm-up")?
+                        .map_or(bench.warm_up, Duration::from_millis),
+                    counters: args.contains("--counters"),
+                    pin: args.opt_value_from_str("--pin")?,
+                    max_rsd: args.opt_value_from_str("--max-rsd")?,
+                    alloc: args.contains("--alloc"),
+                    ..bench
+                };
+
+                let limits = parse_limits(&mut args)?;
+
+                if args.contains("--scale") {
+                    let config = ScaleConfig {
+                        sizes: args.opt_value_from_fn("--sizes", parse_sizes)?,
+                        seed: args.opt_value_from_str("--seed")?.unwrap_or_default(),
+                        from_files: args.contains("--from-files"),
+                        plot: args.contains("--plot"),
+                    };
+
+                    let unsupported = [
+                        ("--all", all),
+                        ("--store", store),
+                        ("--compare", compare),
+                        ("--export", export.is_some()),
+                        ("--isolated", mode == Mode::Isolated),
+                        ("--timeout", limits.timeout.is_some()),
+                        ("--memory-limit", limits.memory_mib.is_some()),
+                    ];
+
+                    if let Some((flag, _)) = unsupported.iter().find(|(_, is_set)| *is_set) {
+                        eprintln!("`{flag}` is not supported with `--scale`.");
+                        process::exit(1);
+                    }
+
+                    let Some(day) = args.opt_free_from_str()?.map(parse_day).transpose()? else {
+                        eprintln!("`--scale` needs a day, e.g. `cargo time 8 --scale`.");
+                        process::exit(1);
+                    };
+
+                    AppArguments::Scale { day, config, bench }
+                } else {
+                    AppArguments::Time {
+                        event,
+                        options: TimeOptions {
+                            day: args.opt_free_from_str()?.map(parse_day).transpose()?,
+                            run_all: all,
+                            store,
+                            compare,
+                            bench,
+                            mode,
+                            limits,
+                            export,
+                        },
+                    }
                 }
             }
+            Some("verify") => AppArguments::Verify {
+                event,
+                release: args.contains("--release"),
+                mode: parse_mode(&mut args)?,
+                limits: parse_limits(&mut args)?,
+                day: args.opt_free_from_str()?.map(parse_day).transpose()?,
+            },
+            Some("gen") => AppArguments::Gen {
+                size: args.opt_value_from_str("--size")?,
+                seed: args.opt_value_from_str("--seed")?.unwrap_or_default(),
+                day: parse_day(args.free_from_str()?)?,
+            },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                day: parse_day(args.free_from_str()?)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                day: parse_day(args.free_from_str()?)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                day: parse_day(args.free_from_str()?)?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                day: parse_day(args.free_from_str()?)?,
+                variant: parse_variant(&mut args)?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                example: args.contains("--example"),
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -93,6 +215,62 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parses how `all` and `verify` run the solutions: `--jobs <n>` runs them in parallel,
+    /// `--isolated` runs them one by one in their own binary.
+    fn parse_mode(args: &mut pico_args::Arguments) -> Result<Mode, pico_args::Error> {
+        let jobs = args.opt_value_from_str("--jobs")?;
+        let isolated = args.contains("--isolated");
+
+        Ok(match jobs {
+            Some(jobs) => Mode::Parallel(jobs),
+            None if isolated => Mode::Isolated,
+            None => Mode::InProcess,
+        })
+    }
+
+    /// Parses the limits that override `data/limits.json` for all days:
+    /// `--timeout <seconds>` and `--memory-limit <MiB>`.
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<Limits, pico_args::Error> {
+        let timeout = args.opt_value_from_fn("--timeout", |secs: &str| {
+            secs.parse()
+                .ok()
+                .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                .filter(|timeout| !timeout.is_zero())
+                .ok_or("expected a positive number of seconds")
+        })?;
+        let memory_mib = args.opt_value_from_fn("--memory-limit", |mib: &str| {
+            mib.parse()
+                .ok()
+                .filter(|&mib| mib > 0)
+                .ok_or("expected a positive number of MiB")
+        })?;
+
+        Ok(Limits {
+            timeout,
+            memory_mib,
+        })
+    }
+
+    /// Parses a comma-separated list of input sizes, e.g. `100,200,400`.
+    fn parse_sizes(s: &str) -> Result<Vec<usize>, std::num::ParseIntError> {
+        s.split(',').map(str::parse).collect()
+    }
+
+    fn parse_variant(args: &mut pico_args::Arguments) -> Result<Option<String>, pico_args::Error> {
+        let variant: Option<String> = args.opt_value_from_str("--variant")?;
+
+        match variant {
+            Some(name) if !layout::is_variant_name(&name) => {
+                Err(pico_args::Error::ArgumentParsingFailed {
+                    cause: format!(
+                        "invalid variant `{name}`, expected lowercase letters, digits and `_`"
+                    ),
+                })
+            }
+            variant => Ok(variant),
+        }
+    }
 }
 
 fn main() {
@@ -102,8 +280,24 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All {
+                event,
+                release,
+                mode,
+                limits,
+            } => all::handle(event, release, mode, &limits),
+            AppArguments::Time { event, options } => time::handle(event, &options),
+            AppArguments::Verify {
+                event,
+                day,
+                release,
+                mode,
+                limits,
+            } => verify::handle(event, day, release, mode, &limits),
+            AppArguments::Scale { day, config, bench } => {
+                time::handle_scale(day, &config, &bench);
+            }
+            AppArguments::Gen { day, size, seed } => generate::handle(day, size, seed),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold {
@@ -118,10 +312,12 @@ fn main() {
             }
             AppArguments::Solve {
                 day,
+                variant,
                 release,
                 dhat,
+                example,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+            } => solve::handle(day, variant, release, dhat, example, submit),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
@@ -131,9 +327,11 @@ fn main() {
                         read::handle(day)
                     }
                     None => {
+                        let event = Event::current();
                         eprintln!(
                             "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            the {}th of december {event}. Please use `scaffold` with a specific day.",
+                            event.days()
                         );
                         process::exit(1)
                     }
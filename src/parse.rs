@@ -0,0 +1,526 @@
+//! Parsing helpers shared by the solutions.
+//!
+//! The helpers work on slices of the input and do not allocate: [`Scanner`] reads integers
+//! and delimiters byte by byte, [`records`], [`fields`] and [`numbers`] split delimited
+//! records and [`grid_size`] checks the shape of a character grid. Parsers return a
+//! [`ParseError`] on malformed input, which points at the offending line and column when a
+//! solution part fails.
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    iter,
+    str::FromStr,
+};
+
+/// An error in a puzzle input, located by its 1-based line and column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    /// The line of the input the error is located in, shown below the message.
+    source_line: String,
+}
+
+impl ParseError {
+    /// Creates an error at the byte `offset` of `input`.
+    pub fn at(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let offset = offset.min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        Self {
+            line: input[..line_start].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            message: message.into(),
+            source_line: input[line_start..line_end].trim_end_matches('\r').into(),
+        }
+    }
+
+    /// Creates an error pointing at `part`, a slice of `input` (e.g. a field returned by `split`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `part` does not lie within `input`.
+    pub fn at_slice(input: &str, part: &str, message: impl Into<String>) -> Self {
+        let offset = (part.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| offset + part.len() <= input.len())
+            .expect("part must be a slice of the input");
+
+        Self::at(input, offset, message)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{number} | {}", self.source_line)?;
+        write!(f, "{gutter} | {}^", " ".repeat(self.column - 1))
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses a field of `input`, ignoring surrounding whitespace.
+///
+/// ```
+/// # use advent_of_code::parse::number;
+/// let input = "12-x4";
+/// let (a, b) = input.split_once('-').unwrap();
+/// assert_eq!(number::<u64>(input, a), Ok(12));
+/// assert_eq!(number::<u64>(input, b).unwrap_err().column, 4);
+/// ```
+pub fn number<T: FromStr>(input: &str, field: &str) -> Result<T, ParseError> {
+    let field = field.trim();
+    field.parse().map_err(|_| {
+        ParseError::at_slice(input, field, format!("expected a number, found `{field}`"))
+    })
+}
+
+/// Splits `input` into records separated by `delimiter`, trimming surrounding whitespace.
+/// Empty records, such as the one after the final newline, are skipped.
+///
+/// ```
+/// # use advent_of_code::parse::records;
+/// let ranges: Vec<&str> = records("11-22, 95-115,\n", ',').collect();
+/// assert_eq!(ranges, ["11-22", "95-115"]);
+/// ```
+pub fn records(input: &str, delimiter: char) -> impl Iterator<Item = &str> {
+    input
+        .split(delimiter)
+        .map(str::trim)
+        .filter(|record| !record.is_empty())
+}
+
+/// Splits `input` into sections separated by blank lines.
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .split("\n\n")
+        .map(|section| section.trim_matches('\n'))
+        .filter(|section| !section.is_empty())
+}
+
+/// Splits a record of `input` into exactly `N` fields separated by `delimiter`. The last field
+/// holds the rest of the record.
+///
+/// ```
+/// # use advent_of_code::parse::fields;
+/// let input = "aaa: you hhh";
+/// assert_eq!(fields(input, input, ':'), Ok(["aaa", " you hhh"]));
+/// assert!(fields::<3>(input, input, ':').is_err());
+/// ```
+pub fn fields<'a, const N: usize>(
+    input: &str,
+    record: &'a str,
+    delimiter: char,
+) -> Result<[&'a str; N], ParseError> {
+    let mut parts = record.splitn(N, delimiter);
+    let mut fields = [""; N];
+
+    for field in &mut fields {
+        *field = parts.next().ok_or_else(|| {
+            ParseError::at_slice(
+                input,
+                record,
+                format!("expected {N} fields separated by `{delimiter}`"),
+            )
+        })?;
+    }
+
+    Ok(fields)
+}
+
+/// Splits a record of `input` into the fields before and after the first `delimiter`.
+pub fn split_pair<'a>(
+    input: &str,
+    record: &'a str,
+    delimiter: char,
+) -> Result<(&'a str, &'a str), ParseError> {
+    let [a, b] = fields(input, record, delimiter)?;
+    Ok((a, b))
+}
+
+/// Parses a record of `input` into exactly `N` numbers separated by `delimiter`.
+///
+/// ```
+/// # use advent_of_code::parse::numbers;
+/// let input = "162,817,812\n";
+/// assert_eq!(numbers(input, input, ','), Ok([162, 817, 812]));
+/// ```
+pub fn numbers<T: FromStr + Copy + Default, const N: usize>(
+    input: &str,
+    record: &str,
+    delimiter: char,
+) -> Result<[T; N], ParseError> {
+    let mut numbers = [T::default(); N];
+
+    for (number, field) in numbers
+        .iter_mut()
+        .zip(fields::<N>(input, record, delimiter)?)
+    {
+        *number = self::number(input, field)?;
+    }
+
+    Ok(numbers)
+}
+
+/// Returns the width and height of a rectangular grid with one row per line.
+///
+/// ```
+/// # use advent_of_code::parse::grid_size;
+/// assert_eq!(grid_size("..@\n@@.\n"), Ok((3, 2)));
+/// assert!(grid_size("..@\n@.\n").is_err());
+/// ```
+pub fn grid_size(input: &str) -> Result<(usize, usize), ParseError> {
+    let mut rows = input.lines();
+    let width = rows
+        .next()
+        .map(str::len)
+        .filter(|&width| width > 0)
+        .ok_or_else(|| ParseError::at(input, 0, "expected a grid"))?;
+
+    let mut height = 1;
+    for row in rows {
+        if row.len() != width {
+            return Err(ParseError::at_slice(
+                input,
+                row,
+                format!("expected a row of {width} columns, found {}", row.len()),
+            ));
+        }
+        height += 1;
+    }
+
+    Ok((width, height))
+}
+
+/// Returns an iterator over the integers in `input`, skipping everything in between.
+/// A `-` directly before a number negates it, if `T` is signed.
+///
+/// ```
+/// # use advent_of_code::parse::integers;
+/// let values: Vec<i32> = integers("p=-3,4 v=5\n").collect::<Result<_, _>>().unwrap();
+/// assert_eq!(values, [-3, 4, 5]);
+/// ```
+pub fn integers<T: Integer>(input: &str) -> impl Iterator<Item = Result<T, ParseError>> {
+    let mut scanner = Scanner::new(input);
+
+    iter::from_fn(move || {
+        loop {
+            scanner.skip_while(|b| !(b.is_ascii_digit() || T::SIGNED && b == b'-'));
+            scanner.peek()?;
+
+            match scanner.integer() {
+                Ok(value) => return Some(Ok(value)),
+                // A lone `-` is not a number.
+                Err(_) if scanner.eat(b'-') => continue,
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    })
+}
+
+/// Integers that a [`Scanner`] can read.
+pub trait Integer: Copy {
+    #[doc(hidden)]
+    const ZERO: Self;
+    #[doc(hidden)]
+    const SIGNED: bool;
+
+    /// Appends a decimal digit, subtracting it for negative numbers. `None` on overflow.
+    #[doc(hidden)]
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty)*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const SIGNED: bool = <$t>::MIN != 0;
+
+            #[inline]
+            fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+                let shifted = self.checked_mul(10)?;
+                if negative {
+                    shifted.checked_sub(digit as $t)
+                } else {
+                    shifted.checked_add(digit as $t)
+                }
+            }
+        }
+    )*};
+}
+
+impl_integer!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+/// A cursor over (a slice of) the input that reads it byte by byte.
+///
+/// ```
+/// # use advent_of_code::parse::Scanner;
+/// let mut scanner = Scanner::new("[.##.] (3) (1,3)");
+/// scanner.expect(b'[').unwrap();
+/// assert_eq!(scanner.take_while(|b| b != b']'), ".##.");
+/// scanner.expect(b']').unwrap();
+/// scanner.skip_whitespace();
+/// assert_eq!(scanner.delimited(b'(', b')').unwrap(), "3");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Scanner<'a> {
+    input: &'a str,
+    pos: usize,
+    end: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            pos: 0,
+            end: input.len(),
+        }
+    }
+
+    /// Creates a scanner over `part`, a slice of `input`. Errors are located in `input`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `part` does not lie within `input`.
+    pub fn within(input: &'a str, part: &str) -> Self {
+        let pos = (part.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| offset + part.len() <= input.len())
+            .expect("part must be a slice of the input");
+
+        Self {
+            input,
+            pos,
+            end: pos + part.len(),
+        }
+    }
+
+    /// The byte offset of the cursor in the input.
+    pub fn offset(&self) -> usize {
+        self.pos
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.pos >= self.end
+    }
+
+    pub fn peek(&self) -> Option<u8> {
+        (self.pos < self.end).then(|| self.input.as_bytes()[self.pos])
+    }
+
+    /// Returns an error at the cursor.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::at(self.input, self.pos, message)
+    }
+
+    /// Advances past `byte` if it is next, returning whether it was.
+    pub fn eat(&mut self, byte: u8) -> bool {
+        let found = self.peek() == Some(byte);
+        self.pos += usize::from(found);
+        found
+    }
+
+    /// Advances past `byte`, or fails if another byte is next.
+    pub fn expect(&mut self, byte: u8) -> Result<(), ParseError> {
+        if self.eat(byte) {
+            Ok(())
+        } else {
+            Err(self.error(format!(
+                "expected `{}`, found {}",
+                char::from(byte),
+                self.describe_next()
+            )))
+        }
+    }
+
+    pub fn skip_while(&mut self, predicate: impl Fn(u8) -> bool) {
+        while self.peek().is_some_and(&predicate) {
+            self.pos += 1;
+        }
+    }
+
+    /// Skips spaces and tabs, but not newlines.
+    pub fn skip_whitespace(&mut self) {
+        self.skip_while(|b| b == b' ' || b == b'\t');
+    }
+
+    /// Returns the bytes up to the first one that does not satisfy `predicate`.
+    pub fn take_while(&mut self, predicate: impl Fn(u8) -> bool) -> &'a str {
+        let start = self.pos;
+        self.skip_while(predicate);
+        // Only ASCII bytes are compared, so a multi-byte character is never split.
+        &self.input[start..self.pos]
+    }
+
+    /// Returns the bytes between `open` and the next `close` on the same line, advancing past both.
+    pub fn delimited(&mut self, open: u8, close: u8) -> Result<&'a str, ParseError> {
+        self.expect(open)?;
+        let contents = self.take_while(|b| b != close && b != b'\n');
+        self.expect(close)?;
+        Ok(contents)
+    }
+
+    /// Reads a decimal integer, with a leading `-` if `T` is signed.
+    pub fn integer<T: Integer>(&mut self) -> Result<T, ParseError> {
+        let start = self.pos;
+        let negative = T::SIGNED && self.eat(b'-');
+        let digits = self.pos;
+        let mut value = T::ZERO;
+
+        while let Some(byte) = self.peek().filter(u8::is_ascii_digit) {
+            value = value
+                .push_digit(byte - b'0', negative)
+                .ok_or_else(|| ParseError::at(self.input, start, "number out of range"))?;
+            self.pos += 1;
+        }
+
+        if self.pos == digits {
+            self.pos = start;
+            return Err(self.error(format!("expected a number, found {}", self.describe_next())));
+        }
+
+        Ok(value)
+    }
+
+    fn describe_next(&self) -> String {
+        match self.input[self.pos..self.end].chars().next() {
+            None => "the end of the input".into(),
+            Some('\n') => "the end of the line".into(),
+            Some(c) => format!("`{c}`"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{
+        ParseError, Scanner, fields, grid_size, integers, number, numbers, records, sections,
+        split_pair,
+    };
+
+    #[test]
+    fn locates_errors() {
+        let input = "1,2\n3,x\n5,6\n";
+        let field = &input[6..7];
+        let error = ParseError::at_slice(input, field, "expected a number");
+
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected a number\n  |\n2 | 3,x\n  |   ^"
+        );
+    }
+
+    #[test]
+    fn locates_errors_at_the_end_of_input() {
+        let error = ParseError::at("1,2", 3, "expected a newline");
+        assert_eq!((error.line, error.column), (1, 4));
+    }
+
+    #[test]
+    fn parses_trimmed_numbers() {
+        let input = "10- 20\n300\n";
+        let (a, b) = input.lines().next().unwrap().split_once('-').unwrap();
+
+        assert_eq!(number::<u32>(input, a), Ok(10));
+        assert_eq!(number::<u32>(input, b), Ok(20));
+
+        let error = number::<u8>(input, &input[7..]).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.message, "expected a number, found `300`");
+    }
+
+    #[test]
+    fn scans_integers() {
+        let mut scanner = Scanner::new("-12,340 x");
+        assert_eq!(scanner.integer::<i32>(), Ok(-12));
+        scanner.expect(b',').unwrap();
+        assert_eq!(scanner.integer::<u16>(), Ok(340));
+        scanner.skip_whitespace();
+
+        let error = scanner.integer::<u8>().unwrap_err();
+        assert_eq!(error.column, 9);
+        assert_eq!(error.message, "expected a number, found `x`");
+        assert_eq!(scanner.offset(), 8);
+    }
+
+    #[test]
+    fn rejects_out_of_range_integers() {
+        assert!(Scanner::new("256").integer::<u8>().is_err());
+        assert_eq!(Scanner::new("255").integer::<u8>(), Ok(255));
+        assert_eq!(Scanner::new("-128").integer::<i8>(), Ok(-128));
+        assert!(Scanner::new("-1").integer::<u8>().is_err());
+    }
+
+    #[test]
+    fn scans_within_slices() {
+        let input = "ab\n[1,x]\n";
+        let line = input.lines().nth(1).unwrap();
+        let mut scanner = Scanner::within(input, line);
+
+        let contents = scanner.delimited(b'[', b']').unwrap();
+        assert!(scanner.is_done());
+
+        let error = numbers::<u8, 2>(input, contents, ',').unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+    }
+
+    #[test]
+    fn reports_missing_delimiters() {
+        let mut scanner = Scanner::new("(1,2\n");
+        let error = scanner.delimited(b'(', b')').unwrap_err();
+        assert_eq!(error.message, "expected `)`, found the end of the line");
+    }
+
+    #[test]
+    fn finds_integers_in_text() {
+        let values: Result<Vec<i64>, _> = integers("x=-1, y=2 - 3\n").collect();
+        assert_eq!(values, Ok(vec![-1, 2, 3]));
+
+        let values: Result<Vec<u64>, _> = integers("a-1b22").collect();
+        assert_eq!(values, Ok(vec![1, 22]));
+    }
+
+    #[test]
+    fn splits_records_and_fields() {
+        let input = "3-5\n10-14\n\n1\n5\n";
+        let [ranges, ids] = sections(input).collect::<Vec<_>>()[..] else {
+            panic!("expected two sections");
+        };
+        assert_eq!(records(ranges, '\n').collect::<Vec<_>>(), ["3-5", "10-14"]);
+        assert_eq!(records(ids, '\n').count(), 2);
+
+        assert_eq!(split_pair(input, "3-5", '-'), Ok(("3", "5")));
+        assert_eq!(fields(input, "1,2,3", ','), Ok(["1", "2,3"]));
+
+        let record = &input[4..9];
+        let error = numbers::<u32, 3>(input, record, '-').unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.message, "expected 3 fields separated by `-`");
+    }
+
+    #[test]
+    fn checks_grid_shapes() {
+        assert_eq!(grid_size("ab\ncd"), Ok((2, 2)));
+
+        let error = grid_size("abc\nde\nfgh\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert!(grid_size("").is_err());
+    }
+}
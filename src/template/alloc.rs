@@ -0,0 +1,275 @@
+//! A global allocator that counts the allocations of a part, selected by `cargo time --alloc`.
+//!
+//! While a part is [tracked](track), the allocator counts its allocations, the bytes they
+//! request and the peak of the bytes live at once, which the runner reports next to the
+//! timings. Only the thread running the part is counted, so the counts do not depend on
+//! other threads, e.g. the watchdog of the [limits](super::limits).
+//!
+//! The allocator is only installed with the `alloc-stats` feature, all other builds keep the
+//! system allocator. It is also replaced by dhat's allocator when profiling with `dhat-heap`.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    collections::HashMap,
+};
+
+use tinyjson::JsonValue;
+
+/// Whether allocations are counted, i.e. [`CountingAlloc`] is the global allocator.
+pub const COUNTING: bool = cfg!(all(feature = "alloc-stats", not(feature = "dhat-heap")));
+
+#[cfg(all(feature = "alloc-stats", not(feature = "dhat-heap")))]
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+thread_local! {
+    /// The counts of the part tracked on this thread, `None` while no part is tracked.
+    static TRACKED: Cell<Option<Counts>> = const { Cell::new(None) };
+}
+
+/// Wraps the system allocator, counting the allocations of tracked parts.
+pub struct CountingAlloc;
+
+/// The running counts of a tracked part.
+#[derive(Clone, Copy, Default)]
+struct Counts {
+    allocations: u64,
+    bytes: u64,
+    /// The bytes allocated minus the bytes freed since [`track`]. Negative when the part
+    /// frees memory that was allocated before it ran.
+    live: i64,
+    peak: i64,
+}
+
+/// The heap usage of a part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// The number of allocations, including reallocations.
+    pub allocations: u64,
+    /// The bytes requested by all allocations.
+    pub bytes: u64,
+    /// The most bytes the part had allocated at once.
+    pub peak_bytes: u64,
+}
+
+impl AllocStats {
+    /// Formats the statistics for output, e.g. `3 allocs, 1.5 KiB, peak 1.0 KiB`.
+    pub fn summary(&self) -> String {
+        if self.allocations == 0 {
+            "no allocations".into()
+        } else {
+            format!(
+                "{} allocs, {}, peak {}",
+                self.allocations,
+                format_bytes(self.bytes),
+                format_bytes(self.peak_bytes)
+            )
+        }
+    }
+}
+
+/// Tracks the allocations of a part, see [`track`].
+pub struct Tracker;
+
+/// Starts counting the allocations of a part on the calling thread, until
+/// [`Tracker::finish`] is called. Returns `None` if the allocations are not counted, i.e.
+/// without the `alloc-stats` feature or when profiling with dhat.
+pub fn track() -> Option<Tracker> {
+    if !COUNTING {
+        return None;
+    }
+
+    TRACKED.with(|tracked| tracked.set(Some(Counts::default())));
+
+    Some(Tracker)
+}
+
+impl Tracker {
+    /// Stops counting and returns the allocations since [`track`].
+    #[allow(clippy::unused_self, clippy::cast_sign_loss)]
+    pub fn finish(self) -> AllocStats {
+        let counts = TRACKED.with(Cell::take).unwrap_or_default();
+
+        AllocStats {
+            allocations: counts.allocations,
+            bytes: counts.bytes,
+            peak_bytes: counts.peak.max(0) as u64,
+        }
+    }
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Counts an allocation of `size` bytes that replaces `freed` bytes, if a part is tracked.
+#[allow(clippy::cast_possible_wrap)]
+fn record(size: usize, freed: usize) {
+    TRACKED.with(|tracked| {
+        if let Some(mut counts) = tracked.get() {
+            counts.allocations += 1;
+            counts.bytes += size as u64;
+            counts.live += size as i64 - freed as i64;
+            counts.peak = counts.peak.max(counts.live);
+            tracked.set(Some(counts));
+        }
+    });
+}
+
+#[allow(clippy::cast_possible_wrap)]
+fn release(size: usize) {
+    TRACKED.with(|tracked| {
+        if let Some(mut counts) = tracked.get() {
+            counts.live -= size as i64;
+            tracked.set(Some(counts));
+        }
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: forwarded from the caller.
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: forwarded from the caller.
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: forwarded from the caller.
+        unsafe { System.dealloc(ptr, layout) };
+        release(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // SAFETY: forwarded from the caller.
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+
+        if !new_ptr.is_null() {
+            record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&AllocStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &AllocStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+        map.insert("bytes".into(), JsonValue::Number(value.bytes as f64));
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for AllocStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected alloc to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|&n| n as u64)
+                .ok_or(format!("Expected alloc.{key} to be a number."))
+        };
+
+        Ok(AllocStats {
+            allocations: number("allocations")?,
+            bytes: number("bytes")?,
+            peak_bytes: number("peak_bytes")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{AllocStats, format_bytes};
+
+    #[test]
+    #[cfg(feature = "alloc-stats")]
+    fn counts_allocations() {
+        use std::hint::black_box;
+
+        use super::track;
+
+        let tracker = track().unwrap();
+        let mut values: Vec<u64> = Vec::with_capacity(128);
+        values.extend(0..256);
+        black_box(&values);
+        drop(values);
+        let stats = tracker.finish();
+
+        // NOTE: the vector grows once, from 1 KiB to 2 KiB.
+        assert_eq!(
+            stats,
+            AllocStats {
+                allocations: 2,
+                bytes: 3 * 1024,
+                peak_bytes: 2 * 1024,
+            }
+        );
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn summarizes_stats() {
+        assert_eq!(AllocStats::default().summary(), "no allocations");
+
+        let stats = AllocStats {
+            allocations: 3,
+            bytes: 1536,
+            peak_bytes: 1024,
+        };
+        assert_eq!(stats.summary(), "3 allocs, 1.5 KiB, peak 1.0 KiB");
+    }
+}
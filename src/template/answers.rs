@@ -0,0 +1,277 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    Day, Event,
+    day::{day_from_json, day_to_json},
+    layout,
+    report::PartReport,
+};
+
+static ANSWERS_FILE_NAME: &str = "answers";
+
+/// Represents the accepted answers for a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// The outcome of comparing a part's answer with the accepted one.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Check<'a> {
+    Pass,
+    Fail { expected: &'a str },
+    Missing,
+}
+
+/// Represents the accepted answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self, event: Event) -> Result<(), Error> {
+        let path = layout::event_file(event, ANSWERS_FILE_NAME);
+        fs::create_dir_all(layout::data_dir(event))?;
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    ///
+    /// # Errors
+    ///
+    /// Returns the reason if the file exists but cannot be read or parsed. Callers must not
+    /// store answers over such a file, as that would discard the answers it holds.
+    pub fn read_from_file(event: Event) -> Result<Self, String> {
+        let path = layout::event_file(event, ANSWERS_FILE_NAME);
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => Answers::try_from(contents)
+                .map_err(|e| format!("{} is malformed: {e}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(format!("could not read {}: {e}", path.display())),
+        }
+    }
+
+    /// Returns the accepted answer for a part, if known.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let answer = self.data.iter().find(|a| a.day == day)?;
+
+        match part {
+            1 => answer.part_1.as_deref(),
+            2 => answer.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Compares the answer of a report with the accepted one.
+    pub fn check(&self, report: &PartReport) -> Check<'_> {
+        match self.get(report.day, report.part) {
+            None => Check::Missing,
+            Some(expected) if report.answer.as_deref() == Some(expected) => Check::Pass,
+            Some(expected) => Check::Fail { expected },
+        }
+    }
+
+    /// Records the accepted answer for a part, replacing any previous value.
+    pub fn set(&mut self, day: Day, part: u8, value: &str) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        let answer = &mut self.data[index];
+
+        match part {
+            1 => answer.part_1 = Some(value.into()),
+            2 => answer.part_2 = Some(value.into()),
+            _ => {}
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        day_to_json(&mut map, value.day);
+
+        for (key, part) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match part {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = day_from_json(json).ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Answer {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{Answers, Check};
+    use crate::{
+        day,
+        template::report::{PartReport, Status},
+    };
+
+    #[test]
+    fn handles_json_answers() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": "3", "part_2": null }] }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(day!(1), 1), Some("3"));
+        assert_eq!(answers.get(day!(1), 2), None);
+        assert_eq!(answers.get(day!(2), 1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": 3 }] }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn sets_answers_in_day_order() {
+        let mut answers = Answers::default();
+        answers.set(day!(4), 2, "43");
+        answers.set(day!(1), 1, "3");
+        answers.set(day!(4), 2, "44");
+
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(1));
+        assert_eq!(answers.get(day!(4), 2), Some("44"));
+    }
+
+    #[test]
+    fn checks_reports() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "3");
+
+        let report = |part, answer: &str| PartReport {
+            day: day!(1),
+            variant: None,
+            part,
+            answer: Some(answer.into()),
+            nanos: 0.0,
+            samples: 1,
+            status: Status::Solved,
+            error: None,
+            stats: None,
+            alloc: None,
+            counters: None,
+        };
+
+        assert_eq!(answers.check(&report(1, "3")), Check::Pass);
+        assert_eq!(
+            answers.check(&report(1, "4")),
+            Check::Fail { expected: "3" }
+        );
+        assert_eq!(answers.check(&report(2, "4")), Check::Missing);
+    }
+
+    #[test]
+    fn round_trips_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(12), 1, "multi\nline");
+
+        let json = JsonValue::from(answers).stringify().unwrap();
+        let parsed = Answers::try_from(json).unwrap();
+        assert_eq!(parsed.get(day!(12), 1), Some("multi\nline"));
+    }
+}
@@ -0,0 +1,639 @@
+//! Native client for the Advent of Code website.
+//!
+//! The session cookie is read from the `AOC_SESSION` environment variable, or from
+//! an `adventofcode.session` file in the home or config directory (the same
+//! locations as aoc-cli). The base URL can be overridden with `AOC_BASE_URL`.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use ureq::Agent;
+
+use crate::template::{Day, layout};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const SESSION_FILE_NAME: &str = "adventofcode.session";
+const USER_AGENT: &str = concat!(
+    "advent_of_code/",
+    env!("CARGO_PKG_VERSION"),
+    " (github.com/MarcusCemes/advent-of-code-2025)"
+);
+
+#[derive(Debug)]
+pub enum AocError {
+    MissingSession,
+    /// The server rejected the session cookie.
+    Unauthorized,
+    /// The puzzle or input does not exist or is not unlocked yet.
+    NotFound,
+    RateLimited {
+        retry_after: Option<Duration>,
+    },
+    HttpStatus(u16),
+    Transport(ureq::Error),
+    IO(io::Error),
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::MissingSession => write!(
+                f,
+                "no session cookie found. Set AOC_SESSION or create ~/.{SESSION_FILE_NAME}."
+            ),
+            AocError::Unauthorized => write!(f, "the session cookie was rejected, log in again."),
+            AocError::NotFound => write!(f, "the puzzle is not available (yet)."),
+            AocError::RateLimited { retry_after } => match retry_after {
+                Some(wait) => write!(f, "rate limited, retry in {}s.", wait.as_secs()),
+                None => write!(f, "rate limited, retry later."),
+            },
+            AocError::HttpStatus(status) => write!(f, "unexpected HTTP status {status}."),
+            AocError::Transport(e) => write!(f, "request failed: {e}"),
+            AocError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+impl From<ureq::Error> for AocError {
+    fn from(e: ureq::Error) -> Self {
+        AocError::Transport(e)
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(e: io::Error) -> Self {
+        AocError::IO(e)
+    }
+}
+
+/// Hint given by the server alongside a wrong answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// The server's verdict on a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect(Option<Hint>),
+    /// An answer was submitted too recently, with the remaining wait time if known.
+    TooRecent(Option<Duration>),
+    /// The part has already been solved or is not unlocked.
+    WrongLevel,
+    Unknown(String),
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer!"),
+            Verdict::Incorrect(None) => write!(f, "That's not the right answer."),
+            Verdict::Incorrect(Some(Hint::TooHigh)) => {
+                write!(f, "That's not the right answer, your answer is too high.")
+            }
+            Verdict::Incorrect(Some(Hint::TooLow)) => {
+                write!(f, "That's not the right answer, your answer is too low.")
+            }
+            Verdict::TooRecent(Some(wait)) => {
+                write!(
+                    f,
+                    "You gave an answer too recently, wait {}s.",
+                    wait.as_secs()
+                )
+            }
+            Verdict::TooRecent(None) => write!(f, "You gave an answer too recently."),
+            Verdict::WrongLevel => write!(f, "This part is already solved or not unlocked."),
+            Verdict::Unknown(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+pub struct AocClient {
+    agent: Agent,
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .user_agent(USER_AGENT)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    /// Creates a client from the environment, see the module documentation.
+    pub fn from_env() -> Result<Self, AocError> {
+        let session = get_session().ok_or(AocError::MissingSession)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &session))
+    }
+
+    /// Fetches the personal puzzle input.
+    pub fn input(&self, day: Day) -> Result<String, AocError> {
+        self.get(&format!("{}/input", self.day_url(day)))
+    }
+
+    /// Fetches the puzzle description, converted to markdown.
+    pub fn puzzle(&self, day: Day) -> Result<String, AocError> {
+        let html = self.get(&self.day_url(day))?;
+        Ok(html_to_markdown(&html))
+    }
+
+    /// Submits an answer for one part and returns the server's verdict.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<Verdict, AocError> {
+        let mut response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(day)))
+            .header("Cookie", &self.cookie())
+            .send_form([("level", part.to_string()), ("answer", answer.to_string())])?;
+
+        check_status(&response)?;
+        let html = response.body_mut().read_to_string()?;
+        Ok(parse_verdict(&html))
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocError> {
+        let mut response = self
+            .agent
+            .get(url)
+            .header("Cookie", &self.cookie())
+            .call()?;
+        check_status(&response)?;
+        Ok(response.body_mut().read_to_string()?)
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, day.event(), day.into_inner())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+fn check_status<B>(response: &ureq::http::Response<B>) -> Result<(), AocError> {
+    match response.status().as_u16() {
+        200..=299 => Ok(()),
+        400 | 401 | 403 => Err(AocError::Unauthorized),
+        404 => Err(AocError::NotFound),
+        429 => Err(AocError::RateLimited {
+            retry_after: response
+                .headers()
+                .get("Retry-After")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse().ok())
+                .map(Duration::from_secs),
+        }),
+        status => Err(AocError::HttpStatus(status)),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub fn read(day: Day) -> Result<(), AocError> {
+    let client = AocClient::from_env()?;
+    let puzzle = client.puzzle(day)?;
+
+    write_data_file(&get_puzzle_path(day), &puzzle)?;
+    println!("{puzzle}");
+    Ok(())
+}
+
+pub fn download(day: Day) -> Result<(), AocError> {
+    let client = AocClient::from_env()?;
+    let input_path = get_input_path(day);
+    let puzzle_path = get_puzzle_path(day);
+
+    write_data_file(&input_path, &client.input(day)?)?;
+    write_data_file(&puzzle_path, &client.puzzle(day)?)?;
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    Ok(())
+}
+
+pub fn submit(day: Day, part: u8, result: &str) -> Result<Verdict, AocError> {
+    let verdict = AocClient::from_env()?.submit(day, part, result)?;
+    println!("{verdict}");
+    Ok(verdict)
+}
+
+fn get_input_path(day: Day) -> PathBuf {
+    layout::data_file("inputs", day)
+}
+
+fn get_puzzle_path(day: Day) -> PathBuf {
+    layout::puzzle_file(day)
+}
+
+/// Writes a data file, creating its folder for events that have none yet.
+fn write_data_file(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session);
+    }
+
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    let home = PathBuf::from(home);
+
+    let config = env::var_os("XDG_CONFIG_HOME").map_or_else(|| home.join(".config"), PathBuf::from);
+
+    [
+        home.join(format!(".{SESSION_FILE_NAME}")),
+        config.join(SESSION_FILE_NAME),
+    ]
+    .iter()
+    .find_map(|path| fs::read_to_string(path).ok())
+    .map(|s| s.trim().to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Interprets the `<article>` of an answer submission page.
+fn parse_verdict(html: &str) -> Verdict {
+    let text = strip_tags(extract_articles(html).first().copied().unwrap_or(html));
+
+    if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        Verdict::Incorrect(hint)
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::TooRecent(parse_wait(&text))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown(text.trim().to_string())
+    }
+}
+
+/// Parses a wait time such as "You have 4m 32s left to wait".
+///
+/// NOTE: the response also says "you have to wait", so the duration is read backwards from
+/// " left to wait" rather than forwards from "you have".
+fn parse_wait(text: &str) -> Option<Duration> {
+    let end = text.find(" left to wait")?;
+
+    let mut seconds = 0;
+    let mut tokens = 0;
+
+    for token in text[..end].split_ascii_whitespace().rev() {
+        let Some((value, unit)) = token
+            .find(|c: char| !c.is_ascii_digit())
+            .filter(|&i| i > 0)
+            .map(|i| token.split_at(i))
+        else {
+            break;
+        };
+        let Ok(value) = value.parse::<u64>() else {
+            break;
+        };
+
+        seconds += match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => break,
+        };
+        tokens += 1;
+    }
+
+    (tokens > 0).then(|| Duration::from_secs(seconds))
+}
+
+/// Returns the inner HTML of every `<article>` element.
+fn extract_articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>') else {
+            break;
+        };
+        let content_start = start + open_end + 1;
+
+        let Some(len) = rest[content_start..].find("</article>") else {
+            break;
+        };
+
+        articles.push(&rest[content_start..content_start + len]);
+        rest = &rest[content_start + len..];
+    }
+
+    articles
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    decode_entities(&text)
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Converts the puzzle articles of a day page into markdown. Only the subset of
+/// HTML used by Advent of Code puzzle descriptions is supported.
+fn html_to_markdown(html: &str) -> String {
+    let mut md = String::new();
+
+    for article in extract_articles(html) {
+        let mut in_pre = false;
+        let mut in_code = false;
+        let mut link: Option<String> = None;
+        let mut rest = article;
+
+        while !rest.is_empty() {
+            let Some(tag_start) = rest.find('<') else {
+                md.push_str(&decode_entities(rest));
+                break;
+            };
+
+            md.push_str(&decode_entities(&rest[..tag_start]));
+
+            let Some(tag_len) = rest[tag_start..].find('>') else {
+                break;
+            };
+
+            let tag = &rest[tag_start + 1..tag_start + tag_len];
+            rest = &rest[tag_start + tag_len + 1..];
+
+            let closing = tag.starts_with('/');
+            let name = tag
+                .trim_start_matches('/')
+                .split(|c: char| c.is_ascii_whitespace())
+                .next()
+                .unwrap_or_default();
+
+            match (name, closing) {
+                ("h2", false) => md.push_str("## "),
+                ("h2" | "p" | "ul", true) => md.push_str("\n\n"),
+                ("pre", false) => {
+                    in_pre = true;
+                    md.push_str("```\n");
+                }
+                ("pre", true) => {
+                    in_pre = false;
+                    if !md.ends_with('\n') {
+                        md.push('\n');
+                    }
+                    md.push_str("```\n\n");
+                }
+                ("code", _) if !in_pre => {
+                    in_code = !closing;
+                    md.push('`');
+                }
+                ("em", _) if !in_pre && !in_code => md.push('*'),
+                ("li", false) => md.push_str("- "),
+                ("li", true) => md.push('\n'),
+                ("a", false) => {
+                    link = tag
+                        .split("href=\"")
+                        .nth(1)
+                        .and_then(|s| s.split('"').next())
+                        .map(str::to_string);
+                    if link.is_some() {
+                        md.push('[');
+                    }
+                }
+                ("a", true) => {
+                    if let Some(href) = link.take() {
+                        md.push_str(&format!("]({href})"));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    md.trim_end().to_string() + "\n"
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+        time::Duration,
+    };
+
+    use super::{AocClient, AocError, Hint, Verdict, html_to_markdown, parse_verdict};
+    use crate::day;
+
+    #[test]
+    fn converts_puzzle_to_markdown() {
+        let html = concat!(
+            "<main><article class=\"day-desc\"><h2>--- Day 1: Test ---</h2>",
+            "<p>Find the <em>answer</em> in <a href=\"/2025/day/1/input\">your input</a>.</p>",
+            "<pre><code>1 &lt; 2\n<em>3</em>\n</code></pre>",
+            "<ul><li>Use <code>x &amp; y</code></li></ul></article>",
+            "<p>Not part of the puzzle.</p></main>",
+        );
+
+        let expected = [
+            "## --- Day 1: Test ---",
+            "",
+            "Find the *answer* in [your input](/2025/day/1/input).",
+            "",
+            "```",
+            "1 < 2",
+            "3",
+            "```",
+            "",
+            "- Use `x & y`",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(html_to_markdown(html), expected);
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        let article = |s: &str| format!("<main><article><p>{s}</p></article></main>");
+
+        assert_eq!(
+            parse_verdict(&article(
+                "That's the right answer! You are one gold star closer."
+            )),
+            Verdict::Correct
+        );
+        assert_eq!(
+            parse_verdict(&article(
+                "That's not the right answer; your answer is too high."
+            )),
+            Verdict::Incorrect(Some(Hint::TooHigh))
+        );
+        assert_eq!(
+            parse_verdict(&article(
+                "That's not the right answer; your answer is too low."
+            )),
+            Verdict::Incorrect(Some(Hint::TooLow))
+        );
+        assert_eq!(
+            parse_verdict(&article(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 34s left to wait. <a href=\"/2025/day/1\">[Return to Day 1]</a>"
+            )),
+            Verdict::TooRecent(Some(Duration::from_secs(34)))
+        );
+        assert_eq!(
+            parse_verdict(&article(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 32s left to wait. <a href=\"/2025/day/1\">[Return to Day 1]</a>"
+            )),
+            Verdict::TooRecent(Some(Duration::from_secs(272)))
+        );
+        assert_eq!(
+            parse_verdict(&article("You don't seem to be solving the right level.")),
+            Verdict::WrongLevel
+        );
+    }
+
+    /// Serves one canned response per expected request, forwarding each request head.
+    fn stub_server(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut head = String::new();
+                let mut content_length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                    head.push_str(&line);
+                }
+
+                let mut body_in = vec![0; content_length];
+                reader.read_exact(&mut body_in).unwrap();
+                head.push_str(&String::from_utf8_lossy(&body_in));
+                tx.send(head).unwrap();
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (url, rx)
+    }
+
+    #[test]
+    fn fetches_input_with_session() {
+        let (url, requests) = stub_server(vec![(200, "1,2,3\n")]);
+        let client = AocClient::new(&url, "secret\n");
+
+        assert_eq!(client.input(day!(8)).unwrap(), "1,2,3\n");
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2025/day/8/input "));
+        assert!(request.contains("session=secret"));
+    }
+
+    #[test]
+    fn uses_event_of_day() {
+        let (url, requests) = stub_server(vec![(200, "")]);
+        let client = AocClient::new(&url, "secret");
+
+        client.input(day!(3, year = 2017)).unwrap();
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2017/day/3/input "));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let page = "<article><p>That's the right answer!</p></article>";
+        let (url, requests) = stub_server(vec![(200, page)]);
+        let client = AocClient::new(&url, "secret");
+
+        assert_eq!(client.submit(day!(1), 2, "42").unwrap(), Verdict::Correct);
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2025/day/1/answer "));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn maps_http_errors() {
+        let (url, _requests) = stub_server(vec![(400, ""), (404, ""), (500, "")]);
+        let client = AocClient::new(&url, "secret");
+
+        assert!(matches!(client.input(day!(1)), Err(AocError::Unauthorized)));
+        assert!(matches!(client.input(day!(1)), Err(AocError::NotFound)));
+        assert!(matches!(
+            client.input(day!(1)),
+            Err(AocError::HttpStatus(500))
+        ));
+    }
+}
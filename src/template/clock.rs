@@ -0,0 +1,9 @@
+//! The wall clock, for timestamps stored in the data files.
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Returns the current time in seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
@@ -1,5 +1,84 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::answers::{Answers, Check};
+use crate::template::limits::Limits;
+use crate::template::report::{PartReport, Status};
+use crate::template::run_multi::{Mode, label, run_multi};
+use crate::template::{ANSI_BOLD, ANSI_RESET, AllDays, Day, Event, layout};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(event: Event, is_release: bool, mode: Mode, limits: &Limits) {
+    let run = run_multi(
+        &AllDays::new_in(event).collect(),
+        is_release,
+        None,
+        mode,
+        limits,
+    );
+
+    // output of parallel runs is buffered per day, summarize it in one place.
+    if matches!(mode, Mode::Parallel(_)) {
+        let solutions: Vec<(Day, Option<String>)> = AllDays::new_in(event)
+            .flat_map(|day| layout::variants(day).into_iter().map(move |v| (day, v)))
+            .collect();
+
+        // NOTE: the summary is still useful without answers, `verify` reports the error.
+        let answers = Answers::read_from_file(event).unwrap_or_else(|e| {
+            eprintln!("Failed to read accepted answers: {e}");
+            Answers::default()
+        });
+
+        print_summary(&solutions, &answers, &run.reports);
+    }
+}
+
+fn print_summary(solutions: &[(Day, Option<String>)], answers: &Answers, reports: &[PartReport]) {
+    let rows: Vec<(String, String, String)> = solutions
+        .iter()
+        .map(|(day, variant)| {
+            let cell = |part| {
+                reports
+                    .iter()
+                    .find(|r| r.day == *day && r.variant == *variant && r.part == part)
+                    .map_or_else(|| "-".into(), |r| format_cell(answers, r))
+            };
+            (label(*day, variant.as_deref()), cell(1), cell(2))
+        })
+        .collect();
+
+    let label_width = rows
+        .iter()
+        .map(|(label, _, _)| label.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let width = rows
+        .iter()
+        .map(|(_, part_1, _)| part_1.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Part 1".len());
+
+    println!();
+    println!("{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!("------");
+    println!("{:<label_width$} | {:<width$} | Part 2", "", "Part 1");
+
+    for (label, part_1, part_2) in rows {
+        println!("{label:<label_width$} | {part_1:<width$} | {part_2}");
+    }
+}
+
+fn format_cell(answers: &Answers, report: &PartReport) -> String {
+    let Some(answer) = &report.answer else {
+        return match report.status {
+            Status::Failed => "✖ failed".into(),
+            Status::Timeout => "✖ TIMEOUT".into(),
+            Status::OutOfMemory => "✖ OOM".into(),
+            _ => "not implemented".into(),
+        };
+    };
+
+    match answers.check(report) {
+        Check::Pass => format!("{answer} ✔"),
+        Check::Fail { expected } => format!("{answer} ✖ (expected {expected})"),
+        Check::Missing => format!("{answer} ?"),
+    }
 }
@@ -0,0 +1,51 @@
+use std::{fs, io, path::PathBuf, process};
+
+use crate::{
+    generators::{self, Generator},
+    template::{Day, layout},
+};
+
+pub fn handle(day: Day, size: Option<usize>, seed: u64) {
+    let Some(generator) = generators::find(day) else {
+        eprintln!("No input generator for day {day} of {}.", day.event());
+        process::exit(1);
+    };
+
+    let size = size.unwrap_or(generator.default_size);
+
+    if let Err(e) = generator.check_size(size) {
+        eprintln!("Failed to generate input: {e}");
+        process::exit(1);
+    }
+
+    match write_input(generator, size, seed) {
+        Ok(path) => {
+            println!(
+                "Generated input with {size} {} into \"{}\"",
+                generator.unit,
+                path.display()
+            );
+        }
+        Err(e) => {
+            eprintln!("Failed to write generated input: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Writes a generated input to `data/generated/`, returning its path.
+/// Fails with [`io::ErrorKind::InvalidInput`] if the generator does not support the size.
+pub fn write_input(generator: &Generator, size: usize, seed: u64) -> io::Result<PathBuf> {
+    generator
+        .check_size(size)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let path = layout::generated_file(generator.day, size, seed);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(&path, generator.generate(size, seed))?;
+    Ok(path)
+}
@@ -1,9 +1,25 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{Day, INPUT_FILE_ENV, INPUT_KIND_ENV, layout};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    day: Day,
+    variant: Option<String>,
+    release: bool,
+    dhat: bool,
+    example: bool,
+    submit_part: Option<u8>,
+) {
+    if example && submit_part.is_some() {
+        eprintln!("Answers to the example cannot be submitted.");
+        process::exit(1);
+    }
+
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--bin".to_string(),
+        layout::variant_bin_name(day, variant.as_deref()),
+    ];
 
     if dhat {
         cmd_args.extend([
@@ -23,8 +39,15 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+    let mut cmd = Command::new("cargo");
+    cmd.args(&cmd_args);
+
+    if example {
+        cmd.env(INPUT_FILE_ENV, layout::data_file("examples", day))
+            .env(INPUT_KIND_ENV, "example");
+    }
+
+    let mut cmd = cmd
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
@@ -1,19 +1,59 @@
 use std::collections::HashSet;
+use std::path::PathBuf;
+use std::process::{self, Command};
+use std::time::Duration;
 
-use crate::template::run_multi::run_multi;
+use crate::generators;
+use crate::template::commands::generate;
+use crate::template::export::{self, Format};
+use crate::template::limits::Limits;
+use crate::template::report::{Status, part_label};
+use crate::template::run_multi::{Mode, child_commands, label, run_multi};
+use crate::template::runner::BenchConfig;
+use crate::template::scaling::{self, Point, ScaleConfig, ScalingReport};
+use crate::template::stats::Change;
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, AllDays, Day, Event, clock, layout, readme_benchmarks,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Options of `cargo time`.
+pub struct TimeOptions {
+    /// The day to bench, or all days of the event.
+    pub day: Option<Day>,
+    /// Whether days that are already fully benched are benched again.
+    pub run_all: bool,
+    /// Whether the timings are stored and the readme is updated.
+    pub store: bool,
+    /// Whether the timings are compared with the stored baseline.
+    pub compare: bool,
+    pub bench: BenchConfig,
+    pub mode: Mode,
+    pub limits: Limits,
+    pub export: Option<Format>,
+}
+
+pub fn handle(event: Event, options: &TimeOptions) {
+    let TimeOptions {
+        day,
+        run_all,
+        store,
+        compare,
+        ref bench,
+        mode,
+        ref limits,
+        export,
+    } = *options;
+
+    let stored_timings = Timings::read_from_file(event);
 
     let days_to_run = day.map_or_else(
         || {
-            if run_all {
-                all_days().collect()
+            if run_all || compare {
+                AllDays::new_in(event).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
+                AllDays::new_in(event)
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
             }
@@ -21,14 +61,30 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let mut timings = run_multi(&days_to_run, true, Some(bench), mode, limits)
+        .timings
+        .unwrap();
+
+    timings.record(clock::now(), current_commit().as_deref());
+
+    let regressions = if compare {
+        print_comparison(&stored_timings, &timings)
+    } else {
+        0
+    };
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(event).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        if let Some(environment) = &merged_timings.environment
+            && readme_benchmarks::update_environment(environment).is_err()
+        {
+            eprintln!("Failed to store the benchmark environment.");
+        }
+
+        match readme_benchmarks::update(event, merged_timings) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +93,185 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    // NOTE: stored timings are exported along with the fresh ones, like they are stored.
+    if let Some(format) = export {
+        match export::store_file(&stored_timings.merge(&timings), format, event) {
+            Ok(path) => println!("Exported benchmarks to {}.", path.display()),
+            Err(e) => eprintln!("Failed to export benchmarks: {e}"),
+        }
+    }
+
+    if regressions > 0 {
+        eprintln!("\n{regressions} part(s) regressed against their baseline.");
+        process::exit(1);
+    }
+}
+
+/// Benches all variants of a day over inputs of increasing size, see [`scaling`].
+pub fn handle_scale(day: Day, config: &ScaleConfig, bench: &BenchConfig) {
+    let generator = generators::find(day);
+    let unit = generator.map_or("items", |generator| generator.unit);
+
+    let inputs = match generator.filter(|_| !config.from_files) {
+        Some(generator) => config
+            .sizes
+            .clone()
+            .unwrap_or_else(|| scaling::default_sizes(generator.default_size))
+            .into_iter()
+            .map(
+                |size| match generate::write_input(generator, size, config.seed) {
+                    Ok(path) => (size, path),
+                    Err(e) => {
+                        eprintln!("Failed to write generated input: {e}");
+                        process::exit(1);
+                    }
+                },
+            )
+            .collect(),
+        None => layout::generated_files(day)
+            .into_iter()
+            .filter(|(size, _)| config.sizes.as_ref().is_none_or(|s| s.contains(size)))
+            .collect::<Vec<(usize, PathBuf)>>(),
+    };
+
+    if inputs.is_empty() {
+        eprintln!(
+            "No inputs of day {day} in data/generated/, add some as e.g. `{day}-1000-1.txt`."
+        );
+        process::exit(1);
+    }
+
+    let mut report = ScalingReport::new(day, unit);
+
+    for variant in layout::variants(day) {
+        let variant = variant.as_deref();
+
+        for (size, path) in &inputs {
+            println!(
+                "{ANSI_BOLD}{} with {size} {unit}{ANSI_RESET}",
+                label(day, variant)
+            );
+            println!("------");
+
+            match child_commands::run_solution(
+                day,
+                variant,
+                Some(path),
+                Some(bench),
+                true,
+                &Limits::default(),
+            ) {
+                Ok(reports) => {
+                    for part_report in reports.unwrap_or_default() {
+                        if part_report.status != Status::Solved {
+                            continue;
+                        }
+                        if let Some(stats) = part_report.stats {
+                            let point = Point { size: *size, stats };
+                            report.add(variant, part_report.part, point);
+                        }
+                    }
+                }
+                Err(e) => eprintln!("Failed to run {}: {e:?}", path.display()),
+            }
+
+            println!();
+        }
+    }
+
+    println!("{ANSI_BOLD}Scaling{ANSI_RESET}");
+    println!("------");
+
+    for series in &report.series {
+        let prefix = format!(
+            "{} {}",
+            label(day, series.variant.as_deref()),
+            part_label(series.part)
+        );
+        match series.exponent() {
+            Some(k) => println!("{prefix}: time ~ n^{k:.2}"),
+            None => println!("{prefix}: {ANSI_ITALIC}not enough sizes{ANSI_RESET}"),
+        }
+    }
+
+    if config.plot {
+        println!();
+        println!("{}", report.plot());
+    }
+
+    println!();
+    match report.store_files() {
+        Ok([csv, json]) => println!(
+            "Stored scaling report in \"{}\" and \"{}\".",
+            csv.display(),
+            json.display()
+        ),
+        Err(e) => eprintln!("Failed to store scaling report: {e}"),
+    }
+}
+
+/// Prints how the fresh benchmarks compare to the last stored ones, returning the number of regressions.
+fn print_comparison(baseline: &Timings, fresh: &Timings) -> usize {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let fmt = |nanos: f64| format!("{:.1?}", Duration::from_nanos(nanos as u64));
+
+    println!();
+    println!("{ANSI_BOLD}Comparison{ANSI_RESET}");
+    println!("------");
+
+    let mut regressions = 0;
+
+    for record in &fresh.history {
+        let variant = record.variant.as_deref();
+        let prefix = format!("{} {}", label(record.day, variant), part_label(record.part));
+
+        let Some(base) = baseline.baseline(record.day, variant, record.part) else {
+            println!("{prefix}: {ANSI_ITALIC}no baseline{ANSI_RESET}");
+            continue;
+        };
+
+        let change = format!(
+            "{} → {} ({:+.1}%)",
+            fmt(base.stats.median),
+            fmt(record.stats.median),
+            record.stats.relative_change(&base.stats) * 100.0
+        );
+
+        let commit = base.commit.as_ref().map_or_else(String::new, |c| {
+            format!(" {ANSI_ITALIC}vs. {c}{ANSI_RESET}")
+        });
+
+        match record.stats.compare(&base.stats) {
+            Change::Regression => {
+                regressions += 1;
+                println!("{prefix}: ✖ regression {change}{commit}");
+            }
+            Change::Improvement => println!("{prefix}: ✔ improvement {change}{commit}"),
+            Change::NoChange => println!("{prefix}: ~ no change {change}{commit}"),
+        }
+    }
+
+    regressions
+}
+
+/// Returns the abbreviated hash of the checked out commit, if the project is a git repository.
+fn current_commit() -> Option<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let commit = git(&["rev-parse", "--short", "HEAD"])?;
+    let is_dirty = git(&["status", "--porcelain"]).is_some_and(|status| !status.is_empty());
+
+    Some(if is_dirty {
+        format!("{commit}-dirty")
+    } else {
+        commit
+    })
 }
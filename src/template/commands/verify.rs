@@ -0,0 +1,60 @@
+use std::{collections::HashSet, process};
+
+use crate::template::answers::{Answers, Check};
+use crate::template::limits::Limits;
+use crate::template::report::{self, Status};
+use crate::template::run_multi::{Mode, label, run_multi};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, AllDays, Day, Event};
+
+pub fn handle(event: Event, day: Option<Day>, is_release: bool, mode: Mode, limits: &Limits) {
+    let answers = match Answers::read_from_file(event) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Failed to read accepted answers: {e}");
+            process::exit(1);
+        }
+    };
+
+    let days_to_run = day.map_or_else(
+        || AllDays::new_in(event).collect(),
+        |day| HashSet::from([day]),
+    );
+    let run = run_multi(&days_to_run, is_release, None, mode, limits);
+
+    println!();
+    println!("{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!("------");
+
+    let mut failures = 0;
+
+    // NOTE: the parse phase has no answer, a failure to parse fails the parts.
+    for report in run.reports.iter().filter(|r| r.part != report::PARSE) {
+        let prefix = format!(
+            "{} Part {}",
+            label(report.day, report.variant.as_deref()),
+            report.part
+        );
+        let actual = match report.status {
+            Status::Failed => "an error",
+            Status::Timeout => "a TIMEOUT",
+            Status::OutOfMemory => "an OOM",
+            _ => report.answer.as_deref().unwrap_or("no answer"),
+        };
+
+        match answers.check(report) {
+            Check::Pass => println!("{prefix}: ✔ pass"),
+            Check::Fail { expected } => {
+                failures += 1;
+                println!("{prefix}: ✖ fail (expected {expected}, got {actual})");
+            }
+            Check::Missing => {
+                println!("{prefix}: ? missing {ANSI_ITALIC}(got {actual}){ANSI_RESET}");
+            }
+        }
+    }
+
+    if failures > 0 {
+        eprintln!("\n{failures} part(s) do not match their accepted answer.");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+/// Environment variable that tells a solution whether it solves an example or a real input.
+pub const INPUT_KIND_ENV: &str = "AOC_INPUT_KIND";
+
+/// Environment variable that overrides parameters of a solution, e.g. `connections=100`.
+pub const PARAMS_ENV: &str = "AOC_PARAMS";
+
+/// The kind of input a solution is run against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    /// An example from the puzzle description.
+    Example,
+    /// A puzzle input or a generated input of the same shape.
+    Real,
+}
+
+/// The context a solution part runs in: the kind of its input and the per-day parameters.
+///
+/// Puzzles sometimes state a parameter that differs between the example and the real input
+/// (e.g. the number of connections on day 8). Parts that need one take a `&Context` as their
+/// second argument and look it up with [`Context::param`].
+///
+/// ```
+/// # use advent_of_code::template::Context;
+/// let ctx = Context::example();
+/// assert_eq!(ctx.param("connections", 10, 1000), 10);
+/// assert_eq!(ctx.with_param("connections", 5).param("connections", 10, 1000), 5);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Context {
+    pub kind: InputKind,
+    params: HashMap<String, String>,
+}
+
+impl Context {
+    pub fn new(kind: InputKind) -> Self {
+        Self {
+            kind,
+            params: HashMap::new(),
+        }
+    }
+
+    pub fn example() -> Self {
+        Self::new(InputKind::Example)
+    }
+
+    pub fn real() -> Self {
+        Self::new(InputKind::Real)
+    }
+
+    /// Creates the context of a solution binary from [`INPUT_KIND_ENV`] and [`PARAMS_ENV`].
+    /// Without them, the input is real and no parameter is overridden.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either variable is malformed.
+    pub fn from_env() -> Self {
+        let kind = match env::var(INPUT_KIND_ENV).as_deref() {
+            Ok("example") => InputKind::Example,
+            Ok("real") | Err(_) => InputKind::Real,
+            Ok(kind) => panic!("{INPUT_KIND_ENV} must be `example` or `real`, got `{kind}`"),
+        };
+
+        let mut ctx = Self::new(kind);
+        if let Ok(params) = env::var(PARAMS_ENV) {
+            for (name, value) in parse_params(&params).unwrap_or_else(|err| panic!("{err}")) {
+                ctx = ctx.with_param(name, value);
+            }
+        }
+        ctx
+    }
+
+    /// Overrides the parameter `name`, whatever the kind of input.
+    #[must_use]
+    pub fn with_param(mut self, name: &str, value: impl ToString) -> Self {
+        self.params.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Returns the parameter `name`: its override if one is set, otherwise `example` or `real`
+    /// depending on the kind of input.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the override cannot be parsed as a `T`.
+    pub fn param<T: FromStr>(&self, name: &str, example: T, real: T) -> T
+    where
+        T::Err: Debug,
+    {
+        match self.params.get(name) {
+            Some(value) => value.parse().unwrap_or_else(|err| {
+                panic!("invalid value `{value}` of parameter {name}: {err:?}")
+            }),
+            None => match self.kind {
+                InputKind::Example => example,
+                InputKind::Real => real,
+            },
+        }
+    }
+}
+
+/// Parses a comma-separated list of `name=value` pairs.
+fn parse_params(params: &str) -> Result<Vec<(&str, &str)>, String> {
+    params
+        .split(',')
+        .map(str::trim)
+        .filter(|param| !param.is_empty())
+        .map(|param| match param.split_once('=') {
+            Some((name, value)) if !name.trim().is_empty() => Ok((name.trim(), value.trim())),
+            _ => Err(format!(
+                "invalid parameter `{param}` in {PARAMS_ENV}, expected `name=value`"
+            )),
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Context, parse_params};
+
+    #[test]
+    fn picks_parameters_by_input_kind() {
+        assert_eq!(Context::example().param("rows", 3, 4), 3);
+        assert_eq!(Context::real().param("rows", 3, 4), 4);
+        assert_eq!(Context::real().with_param("rows", 7).param("rows", 3, 4), 7);
+        assert_eq!(Context::real().with_param("cols", 7).param("rows", 3, 4), 4);
+    }
+
+    #[test]
+    fn parses_parameter_overrides() {
+        assert_eq!(
+            parse_params("connections=100, rows = 5,"),
+            Ok(vec![("connections", "100"), ("rows", "5")])
+        );
+        assert_eq!(parse_params(""), Ok(vec![]));
+        assert!(parse_params("connections").is_err());
+        assert!(parse_params("=5").is_err());
+    }
+}
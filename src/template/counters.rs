@@ -0,0 +1,457 @@
+//! Hardware performance counters of benchmarked parts, read with `perf_event_open` on Linux.
+//!
+//! `cargo time --counters` counts instructions, cycles, branches and cache accesses of the
+//! benchmarked parts in user space, and reports them per iteration along with the derived
+//! IPC and miss rates. Counters that the CPU, the kernel or the sandbox do not provide are
+//! left out, and on other systems no counters are available at all.
+use std::collections::HashMap;
+
+use tinyjson::JsonValue;
+
+/// The counters of a part, averaged per iteration. Unavailable counters are `None`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Counters {
+    pub instructions: Option<f64>,
+    pub cycles: Option<f64>,
+    pub branches: Option<f64>,
+    pub branch_misses: Option<f64>,
+    /// Reads of the L1 data cache.
+    pub l1d_reads: Option<f64>,
+    pub l1d_misses: Option<f64>,
+    /// References to the last level cache.
+    pub llc_references: Option<f64>,
+    pub llc_misses: Option<f64>,
+}
+
+impl Counters {
+    /// Instructions per cycle.
+    pub fn ipc(&self) -> Option<f64> {
+        ratio(self.instructions, self.cycles)
+    }
+
+    pub fn branch_miss_rate(&self) -> Option<f64> {
+        ratio(self.branch_misses, self.branches)
+    }
+
+    pub fn l1d_miss_rate(&self) -> Option<f64> {
+        ratio(self.l1d_misses, self.l1d_reads)
+    }
+
+    pub fn llc_miss_rate(&self) -> Option<f64> {
+        ratio(self.llc_misses, self.llc_references)
+    }
+
+    /// Formats the available counters for output, e.g.
+    /// `IPC 3.12 · 1.4M instructions · 0.3% branch misses · 1.1% L1d misses · 12.0% LLC misses`.
+    pub fn summary(&self) -> String {
+        let percent =
+            |rate: Option<f64>, name: &str| rate.map(|r| format!("{:.1}% {name}", r * 100.0));
+
+        [
+            self.ipc().map(|ipc| format!("IPC {ipc:.2}")),
+            self.instructions
+                .map(|n| format!("{} instructions", format_count(n))),
+            percent(self.branch_miss_rate(), "branch misses"),
+            percent(self.l1d_miss_rate(), "L1d misses"),
+            percent(self.llc_miss_rate(), "LLC misses"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" · ")
+    }
+}
+
+fn ratio(numerator: Option<f64>, denominator: Option<f64>) -> Option<f64> {
+    match (numerator, denominator) {
+        (Some(n), Some(d)) if d > 0.0 => Some(n / d),
+        _ => None,
+    }
+}
+
+/// Formats a count with a metric suffix, e.g. `1.4M`.
+fn format_count(count: f64) -> String {
+    match count {
+        c if c >= 1e9 => format!("{:.1}G", c / 1e9),
+        c if c >= 1e6 => format!("{:.1}M", c / 1e6),
+        c if c >= 1e3 => format!("{:.1}k", c / 1e3),
+        c => format!("{c:.0}"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The counters that are read, in the order of the fields of [`Counters`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Event {
+    Instructions,
+    Cycles,
+    Branches,
+    BranchMisses,
+    L1dReads,
+    L1dMisses,
+    LlcReferences,
+    LlcMisses,
+}
+
+const EVENTS: [Event; 8] = [
+    Event::Instructions,
+    Event::Cycles,
+    Event::Branches,
+    Event::BranchMisses,
+    Event::L1dReads,
+    Event::L1dMisses,
+    Event::LlcReferences,
+    Event::LlcMisses,
+];
+
+impl Event {
+    fn field(self, counters: &mut Counters) -> &mut Option<f64> {
+        match self {
+            Event::Instructions => &mut counters.instructions,
+            Event::Cycles => &mut counters.cycles,
+            Event::Branches => &mut counters.branches,
+            Event::BranchMisses => &mut counters.branch_misses,
+            Event::L1dReads => &mut counters.l1d_reads,
+            Event::L1dMisses => &mut counters.l1d_misses,
+            Event::LlcReferences => &mut counters.llc_references,
+            Event::LlcMisses => &mut counters.llc_misses,
+        }
+    }
+}
+
+/// A set of counters of the current thread, counting while enabled.
+pub struct CounterGroup {
+    counters: Vec<(Event, sys::Counter)>,
+}
+
+impl CounterGroup {
+    /// Opens all counters that are available.
+    ///
+    /// # Errors
+    ///
+    /// Returns the reason if no counter is available, e.g. when `perf_event_paranoid`
+    /// forbids them or the system is not Linux.
+    pub fn open() -> Result<Self, String> {
+        let mut counters = vec![];
+        let mut error = None;
+
+        for event in EVENTS {
+            match sys::Counter::open(event) {
+                Ok(counter) => counters.push((event, counter)),
+                Err(e) => error = Some(e),
+            }
+        }
+
+        match error {
+            Some(e) if counters.is_empty() => Err(e),
+            _ => Ok(CounterGroup { counters }),
+        }
+    }
+
+    /// Resets the counters and starts counting.
+    pub fn enable(&self) {
+        for (_, counter) in &self.counters {
+            counter.enable();
+        }
+    }
+
+    pub fn disable(&self) {
+        for (_, counter) in &self.counters {
+            counter.disable();
+        }
+    }
+
+    /// Reads the counters, averaged over the iterations counted since [`CounterGroup::enable`].
+    #[allow(clippy::cast_precision_loss)]
+    pub fn read(&self, iterations: u64) -> Counters {
+        let mut counters = Counters::default();
+
+        for (event, counter) in &self.counters {
+            *event.field(&mut counters) = counter.read().map(|n| n / iterations.max(1) as f64);
+        }
+
+        counters
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use std::{
+        fs::File,
+        io::{self, Read},
+        mem,
+        os::fd::{AsRawFd, FromRawFd},
+    };
+
+    use super::Event;
+
+    const PERF_TYPE_HARDWARE: u32 = 0;
+    const PERF_TYPE_HW_CACHE: u32 = 3;
+
+    const PERF_COUNT_HW_CPU_CYCLES: u64 = 0;
+    const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+    const PERF_COUNT_HW_CACHE_REFERENCES: u64 = 2;
+    const PERF_COUNT_HW_CACHE_MISSES: u64 = 3;
+    const PERF_COUNT_HW_BRANCH_INSTRUCTIONS: u64 = 4;
+    const PERF_COUNT_HW_BRANCH_MISSES: u64 = 5;
+
+    const PERF_COUNT_HW_CACHE_L1D: u64 = 0;
+    const PERF_COUNT_HW_CACHE_OP_READ: u64 = 0;
+    const PERF_COUNT_HW_CACHE_RESULT_ACCESS: u64 = 0;
+    const PERF_COUNT_HW_CACHE_RESULT_MISS: u64 = 1;
+
+    const PERF_FORMAT_TOTAL_TIME_ENABLED: u64 = 1 << 0;
+    const PERF_FORMAT_TOTAL_TIME_RUNNING: u64 = 1 << 1;
+
+    const FLAG_DISABLED: u64 = 1 << 0;
+    const FLAG_EXCLUDE_KERNEL: u64 = 1 << 5;
+    const FLAG_EXCLUDE_HV: u64 = 1 << 6;
+
+    const PERF_EVENT_IOC_ENABLE: libc::c_ulong = 0x2400;
+    const PERF_EVENT_IOC_DISABLE: libc::c_ulong = 0x2401;
+    const PERF_EVENT_IOC_RESET: libc::c_ulong = 0x2403;
+
+    /// The first version of `struct perf_event_attr`, which the kernel still accepts.
+    #[repr(C)]
+    #[derive(Default)]
+    struct PerfEventAttr {
+        kind: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        wakeup_events: u32,
+        bp_type: u32,
+        config1: u64,
+    }
+
+    pub struct Counter {
+        file: File,
+    }
+
+    impl Counter {
+        pub fn open(event: Event) -> Result<Self, String> {
+            let cache = |cache: u64, result: u64| {
+                (
+                    PERF_TYPE_HW_CACHE,
+                    cache | PERF_COUNT_HW_CACHE_OP_READ << 8 | result << 16,
+                )
+            };
+
+            let (kind, config) = match event {
+                Event::Instructions => (PERF_TYPE_HARDWARE, PERF_COUNT_HW_INSTRUCTIONS),
+                Event::Cycles => (PERF_TYPE_HARDWARE, PERF_COUNT_HW_CPU_CYCLES),
+                Event::Branches => (PERF_TYPE_HARDWARE, PERF_COUNT_HW_BRANCH_INSTRUCTIONS),
+                Event::BranchMisses => (PERF_TYPE_HARDWARE, PERF_COUNT_HW_BRANCH_MISSES),
+                Event::L1dReads => {
+                    cache(PERF_COUNT_HW_CACHE_L1D, PERF_COUNT_HW_CACHE_RESULT_ACCESS)
+                }
+                Event::L1dMisses => cache(PERF_COUNT_HW_CACHE_L1D, PERF_COUNT_HW_CACHE_RESULT_MISS),
+                Event::LlcReferences => (PERF_TYPE_HARDWARE, PERF_COUNT_HW_CACHE_REFERENCES),
+                Event::LlcMisses => (PERF_TYPE_HARDWARE, PERF_COUNT_HW_CACHE_MISSES),
+            };
+
+            #[allow(clippy::cast_possible_truncation)]
+            let attr = PerfEventAttr {
+                kind,
+                size: mem::size_of::<PerfEventAttr>() as u32,
+                config,
+                read_format: PERF_FORMAT_TOTAL_TIME_ENABLED | PERF_FORMAT_TOTAL_TIME_RUNNING,
+                // NOTE: only user space is counted, which unprivileged processes may do.
+                flags: FLAG_DISABLED | FLAG_EXCLUDE_KERNEL | FLAG_EXCLUDE_HV,
+                ..PerfEventAttr::default()
+            };
+
+            // SAFETY: `attr` is a valid `perf_event_attr` of the size it declares, and the
+            // arguments select the calling thread on any CPU, without a group or flags.
+            let fd = unsafe {
+                libc::syscall(
+                    libc::SYS_perf_event_open,
+                    &raw const attr,
+                    0 as libc::pid_t,
+                    -1 as libc::c_int,
+                    -1 as libc::c_int,
+                    0 as libc::c_ulong,
+                )
+            };
+
+            if fd < 0 {
+                return Err(format!(
+                    "perf_event_open failed: {}",
+                    io::Error::last_os_error()
+                ));
+            }
+
+            // SAFETY: the file descriptor was just opened and is owned by nothing else.
+            #[allow(clippy::cast_possible_truncation)]
+            let file = unsafe { File::from_raw_fd(fd as libc::c_int) };
+
+            Ok(Counter { file })
+        }
+
+        fn ioctl(&self, request: libc::c_ulong) {
+            // SAFETY: the requests take no argument and the descriptor is a perf event.
+            unsafe { libc::ioctl(self.file.as_raw_fd(), request, 0) };
+        }
+
+        pub fn enable(&self) {
+            self.ioctl(PERF_EVENT_IOC_RESET);
+            self.ioctl(PERF_EVENT_IOC_ENABLE);
+        }
+
+        pub fn disable(&self) {
+            self.ioctl(PERF_EVENT_IOC_DISABLE);
+        }
+
+        /// Reads the count, scaled up if the counter was multiplexed with others.
+        /// Returns `None` if the counter never ran.
+        #[allow(clippy::cast_precision_loss)]
+        pub fn read(&self) -> Option<f64> {
+            let mut buf = [0_u8; 24];
+            (&self.file).read_exact(&mut buf).ok()?;
+
+            let [value, enabled, running] =
+                [0, 8, 16].map(|i| u64::from_ne_bytes(buf[i..i + 8].try_into().unwrap()));
+
+            (running > 0).then(|| value as f64 * enabled as f64 / running as f64)
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod sys {
+    use super::Event;
+
+    pub struct Counter;
+
+    impl Counter {
+        pub fn open(_: Event) -> Result<Self, String> {
+            Err("hardware counters are only available on Linux".into())
+        }
+
+        pub fn enable(&self) {}
+
+        pub fn disable(&self) {}
+
+        pub fn read(&self) -> Option<f64> {
+            None
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Counters> for JsonValue {
+    fn from(value: &Counters) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        let mut counters = *value;
+
+        for event in EVENTS {
+            if let Some(count) = *event.field(&mut counters) {
+                map.insert(event.key().into(), JsonValue::Number(count));
+            }
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Counters {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected counters to be a JSON object.")?;
+
+        let mut counters = Counters::default();
+
+        for event in EVENTS {
+            *event.field(&mut counters) = match json.get(event.key()) {
+                None => None,
+                Some(v) => Some(
+                    *v.get::<f64>()
+                        .ok_or(format!("Expected counters.{} to be a number.", event.key()))?,
+                ),
+            };
+        }
+
+        Ok(counters)
+    }
+}
+
+impl Event {
+    fn key(self) -> &'static str {
+        match self {
+            Event::Instructions => "instructions",
+            Event::Cycles => "cycles",
+            Event::Branches => "branches",
+            Event::BranchMisses => "branch_misses",
+            Event::L1dReads => "l1d_reads",
+            Event::L1dMisses => "l1d_misses",
+            Event::LlcReferences => "llc_references",
+            Event::LlcMisses => "llc_misses",
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::hint::black_box;
+
+    use tinyjson::JsonValue;
+
+    use super::{CounterGroup, Counters};
+
+    fn counters() -> Counters {
+        Counters {
+            instructions: Some(3_000_000.0),
+            cycles: Some(1_000_000.0),
+            branches: Some(200_000.0),
+            branch_misses: Some(1_000.0),
+            l1d_reads: Some(500_000.0),
+            l1d_misses: Some(5_000.0),
+            llc_references: None,
+            llc_misses: None,
+        }
+    }
+
+    #[test]
+    fn derives_rates() {
+        let counters = counters();
+        assert_eq!(counters.ipc(), Some(3.0));
+        assert_eq!(counters.branch_miss_rate(), Some(0.005));
+        assert_eq!(counters.l1d_miss_rate(), Some(0.01));
+        assert_eq!(counters.llc_miss_rate(), None);
+        assert_eq!(
+            counters.summary(),
+            "IPC 3.00 · 3.0M instructions · 0.5% branch misses · 1.0% L1d misses"
+        );
+        assert_eq!(Counters::default().summary(), "");
+    }
+
+    #[test]
+    fn round_trips_counters() {
+        let counters = counters();
+        let json = JsonValue::from(&counters);
+        assert_eq!(Counters::try_from(&json), Ok(counters));
+    }
+
+    #[test]
+    fn degrades_without_counters() {
+        // NOTE: counters are often unavailable in containers and CI, both outcomes are fine.
+        if let Ok(group) = CounterGroup::open() {
+            group.enable();
+            black_box((0..1000_u64).sum::<u64>());
+            group.disable();
+
+            let counters = group.read(10);
+            assert!(counters.instructions.is_none_or(|n| n >= 0.0));
+        }
+    }
+}
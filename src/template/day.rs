@@ -1,17 +1,23 @@
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use tinyjson::JsonValue;
+
+use crate::template::Event;
+
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// A valid day number of advent (i.e. an integer in range 1 to the length of its [`Event`]).
+/// Days created without an explicit event belong to the current event.
 ///
 /// # Display
-/// This value displays as a two digit number.
+/// This value displays as a two digit number, without the year.
 ///
 /// ```
 /// # use advent_of_code::Day;
@@ -19,32 +25,54 @@ const SERVER_UTC_OFFSET: i32 = -5;
 /// assert_eq!(day.to_string(), "08")
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Day(u8);
+pub struct Day {
+    event: Event,
+    day: u8,
+}
 
 impl Day {
-    /// Creates a [`Day`] from the provided value if it's in the valid range,
-    /// returns [`None`] otherwise.
+    /// Creates a [`Day`] from the provided value if it's in the valid range
+    /// of the current event, returns [`None`] otherwise.
     pub const fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        Self::new_in(Event::current(), day)
+    }
+
+    /// Creates a [`Day`] from the provided value if it's in the valid range
+    /// of the given event, returns [`None`] otherwise.
+    pub const fn new_in(event: Event, day: u8) -> Option<Self> {
+        if day == 0 || day > event.days() {
             return None;
         }
-        Some(Self(day))
+        Some(Self { event, day })
+    }
+
+    /// Parses a day number of the given event.
+    pub fn parse_in(event: Event, s: &str) -> Result<Self, DayFromStrError> {
+        let day = s.parse().map_err(|_| DayFromStrError(event))?;
+        Self::new_in(event, day).ok_or(DayFromStrError(event))
     }
 
     /// Converts the [`Day`] into an [`u8`].
     pub fn into_inner(self) -> u8 {
-        self.0
+        self.day
+    }
+
+    /// Returns the event the [`Day`] belongs to.
+    pub fn event(self) -> Event {
+        self.event
     }
 }
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's a day of the current event in december, `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
+        let event = Event::current();
+
+        if today.year() == i32::from(event.year()) && today.month() == 12 {
+            Self::new_in(event, u8::try_from(today.day()).ok()?)
         } else {
             None
         }
@@ -53,19 +81,19 @@ impl Day {
 
 impl Display for Day {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:02}", self.0)
+        write!(f, "{:02}", self.day)
     }
 }
 
 impl PartialEq<u8> for Day {
     fn eq(&self, other: &u8) -> bool {
-        self.0.eq(other)
+        self.day.eq(other)
     }
 }
 
 impl PartialOrd<u8> for Day {
     fn partial_cmp(&self, other: &u8) -> Option<std::cmp::Ordering> {
-        self.0.partial_cmp(other)
+        self.day.partial_cmp(other)
     }
 }
 
@@ -75,39 +103,48 @@ impl FromStr for Day {
     type Err = DayFromStrError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let day = s.parse().map_err(|_| DayFromStrError)?;
-        Self::new(day).ok_or(DayFromStrError)
+        Self::parse_in(Event::current(), s)
     }
 }
 
-/// An error which can be returned when parsing a [`Day`].
+/// An error which can be returned when parsing a [`Day`] of an [`Event`].
 #[derive(Debug)]
-pub struct DayFromStrError;
+pub struct DayFromStrError(Event);
 
 impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(
+            f,
+            "expecting a day number between 1 and {} for {}",
+            self.0.days(),
+            self.0
+        )
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the current event, from the 1st to the last.
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of an event, from the 1st to the last.
 pub struct AllDays {
+    event: Event,
     current: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::new_in(Event::current())
+    }
+
+    pub fn new_in(event: Event) -> Self {
+        Self { event, current: 1 }
     }
 }
 
@@ -115,11 +152,14 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.event.days() {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
-        let day = Day(self.current);
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the event length.
+        let day = Day {
+            event: self.event,
+            day: self.current,
+        };
         self.current += 1;
 
         Some(day)
@@ -128,54 +168,90 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
-/// Creates a [`Day`] value in a const context.
+/// Reads a [`Day`] from the `day` and optional `year` keys of a JSON object.
+/// Objects without a year belong to the current event.
+pub(crate) fn day_from_json(json: &HashMap<String, JsonValue>) -> Option<Day> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let event = match json.get("year") {
+        None => Event::current(),
+        Some(year) => Event::new(*year.get::<f64>()? as u16)?,
+    };
+
+    Day::parse_in(event, json.get("day")?.get::<String>()?).ok()
+}
+
+/// Writes a [`Day`] as the `day` and `year` keys of a JSON object.
+pub(crate) fn day_to_json(map: &mut HashMap<String, JsonValue>, day: Day) {
+    map.insert("day".into(), JsonValue::String(day.to_string()));
+    map.insert(
+        "year".into(),
+        JsonValue::Number(f64::from(day.event().year())),
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Day`] value in a const context, optionally for a given year.
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {
         const {
             $crate::template::Day::new($day)
-                .expect("invalid day number, expecting a value between 1 and 25")
+                .expect("invalid day number, expecting a day of the current event")
+        }
+    };
+    ($day:expr, year = $year:expr) => {
+        const {
+            match $crate::template::Event::new($year) {
+                Some(event) => $crate::template::Day::new_in(event, $day)
+                    .expect("invalid day number, expecting a day of the given event"),
+                None => panic!("invalid year, expecting a year with an event"),
+            }
         }
     };
 }
 
 /* -------------------------------------------------------------------------- */
 
-#[cfg(feature = "test_lib")]
+#[cfg(all(test, feature = "test_lib"))]
 mod tests {
-    use super::{Day, all_days};
+    use super::{AllDays, Day, all_days};
+    use crate::template::Event;
 
     #[test]
     fn all_days_iterator() {
         let mut iter = all_days();
 
-        assert_eq!(iter.next(), Some(Day(1)));
-        assert_eq!(iter.next(), Some(Day(2)));
-        assert_eq!(iter.next(), Some(Day(3)));
-        assert_eq!(iter.next(), Some(Day(4)));
-        assert_eq!(iter.next(), Some(Day(5)));
-        assert_eq!(iter.next(), Some(Day(6)));
-        assert_eq!(iter.next(), Some(Day(7)));
-        assert_eq!(iter.next(), Some(Day(8)));
-        assert_eq!(iter.next(), Some(Day(9)));
-        assert_eq!(iter.next(), Some(Day(10)));
-        assert_eq!(iter.next(), Some(Day(11)));
-        assert_eq!(iter.next(), Some(Day(12)));
-        assert_eq!(iter.next(), Some(Day(13)));
-        assert_eq!(iter.next(), Some(Day(14)));
-        assert_eq!(iter.next(), Some(Day(15)));
-        assert_eq!(iter.next(), Some(Day(16)));
-        assert_eq!(iter.next(), Some(Day(17)));
-        assert_eq!(iter.next(), Some(Day(18)));
-        assert_eq!(iter.next(), Some(Day(19)));
-        assert_eq!(iter.next(), Some(Day(20)));
-        assert_eq!(iter.next(), Some(Day(21)));
-        assert_eq!(iter.next(), Some(Day(22)));
-        assert_eq!(iter.next(), Some(Day(23)));
-        assert_eq!(iter.next(), Some(Day(24)));
-        assert_eq!(iter.next(), Some(Day(25)));
+        assert_eq!(iter.next(), Day::new(1));
+        assert_eq!(iter.next(), Day::new(2));
+        assert_eq!(iter.next(), Day::new(3));
+        assert_eq!(iter.next(), Day::new(4));
+        assert_eq!(iter.next(), Day::new(5));
+        assert_eq!(iter.next(), Day::new(6));
+        assert_eq!(iter.next(), Day::new(7));
+        assert_eq!(iter.next(), Day::new(8));
+        assert_eq!(iter.next(), Day::new(9));
+        assert_eq!(iter.next(), Day::new(10));
+        assert_eq!(iter.next(), Day::new(11));
+        assert_eq!(iter.next(), Day::new(12));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn all_days_iterator_for_long_events() {
+        let event = Event::new(2024).unwrap();
+        assert_eq!(AllDays::new_in(event).count(), 25);
+        assert_eq!(AllDays::new_in(event).last(), Day::new_in(event, 25));
+    }
+
+    #[test]
+    fn validates_against_event() {
+        assert_eq!(Day::new(12).unwrap().into_inner(), 12);
+        assert_eq!(Day::new(13), None);
+        assert_eq!(Day::new(0), None);
+        assert!(Day::new_in(Event::new(2024).unwrap(), 25).is_some());
+        assert!("13".parse::<Day>().is_err());
+    }
 }
 
 /* -------------------------------------------------------------------------- */
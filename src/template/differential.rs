@@ -0,0 +1,353 @@
+//! Differential testing of the variants of a solution.
+//!
+//! Every day with more than one solution (e.g. `src/bin/08.rs` and `src/bin/08_fast.rs`) is
+//! linked into the `variants` test by `build.rs`. The test runs all variants of a day on its
+//! example, its puzzle input, the inputs in `data/generated/` and a few small inputs of its
+//! [generator](crate::generators), and fails if they disagree. Each
+//! disagreement comes with a reproducer, the input minimised to the lines that still provoke it.
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+};
+
+use crate::{
+    generators,
+    template::{Context, Day, layout, run_multi::label, runner::IntoAnswer},
+};
+
+/// Seeds of the inputs generated for each comparison.
+const SEEDS: [u64; 3] = [1, 2, 3];
+
+/// The answer of a part converted to a string, see [`IntoAnswer`].
+type Answer = Result<Option<String>, String>;
+
+/// A variant of a solution, with its answers converted to strings.
+pub struct Candidate {
+    pub day: Day,
+    pub variant: Option<&'static str>,
+    pub part_one: fn(&str, &Context) -> Answer,
+    pub part_two: fn(&str, &Context) -> Answer,
+}
+
+impl Candidate {
+    fn solve(&self, part: u8, input: &str, ctx: &Context) -> Outcome {
+        let func = if part == 1 {
+            self.part_one
+        } else {
+            self.part_two
+        };
+
+        match panic::catch_unwind(AssertUnwindSafe(|| func(input, ctx))) {
+            Ok(Ok(answer)) => Outcome::Answer(answer),
+            Ok(Err(error)) => Outcome::Failed(error),
+            Err(_) => Outcome::Panicked,
+        }
+    }
+}
+
+/// Converts the return value of a part for a [`Candidate`].
+pub fn answer(value: impl IntoAnswer) -> Answer {
+    value
+        .into_answer()
+        .map(|answer| answer.map(|a| a.to_string()))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Answer(Option<String>),
+    /// The part returned an error.
+    Failed(String),
+    Panicked,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Answer(Some(answer)) => write!(f, "{answer}"),
+            Outcome::Answer(None) => write!(f, "no answer"),
+            Outcome::Failed(error) => write!(f, "failed: {}", error.lines().next().unwrap_or("")),
+            Outcome::Panicked => write!(f, "panicked"),
+        }
+    }
+}
+
+/// An input the variants are compared on.
+pub struct Input {
+    /// Where the input comes from, e.g. `data/examples/08.txt`.
+    pub source: String,
+    pub contents: String,
+    /// The context the variants solve the input in.
+    pub ctx: Context,
+}
+
+impl Input {
+    fn read(path: &Path, ctx: Context) -> Option<Input> {
+        Some(Input {
+            source: path.display().to_string(),
+            contents: fs::read_to_string(path).ok()?,
+            ctx,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Disagreement {
+    pub day: Day,
+    pub part: u8,
+    pub source: String,
+    pub outcomes: Vec<(Option<&'static str>, Outcome)>,
+    /// The smallest input found that provokes the same disagreement.
+    pub reproducer: String,
+}
+
+impl Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Day {} Part {}: variants disagree on {}",
+            self.day, self.part, self.source
+        )?;
+
+        for (variant, outcome) in &self.outcomes {
+            writeln!(f, "  {}: {outcome}", label(self.day, *variant))?;
+        }
+
+        writeln!(f, "Reproducer ({} lines):", self.reproducer.lines().count())?;
+        write!(f, "{}", self.reproducer)
+    }
+}
+
+/// Returns the inputs a day's variants are compared on. Missing files are skipped.
+pub fn inputs(day: Day) -> Vec<Input> {
+    let mut inputs: Vec<Input> =
+        Input::read(&layout::data_file("examples", day), Context::example())
+            .into_iter()
+            .collect();
+
+    let mut paths = vec![layout::data_file("inputs", day)];
+    paths.extend(
+        layout::generated_files(day)
+            .into_iter()
+            .map(|(_, path)| path),
+    );
+    inputs.extend(
+        paths
+            .iter()
+            .filter_map(|path| Input::read(path, Context::real())),
+    );
+
+    // Small inputs keep the comparison fast and the reproducers short.
+    if let Some(generator) = generators::find(day) {
+        let size = (generator.default_size / 10).max(10);
+        inputs.extend(SEEDS.map(|seed| Input {
+            source: format!("generated input of size {size} with seed {seed}"),
+            contents: generator.generate(size, seed),
+            ctx: Context::real(),
+        }));
+    }
+
+    inputs
+}
+
+/// Runs all candidates of one day on an input and reports the parts they disagree on.
+pub fn check(candidates: &[&Candidate], input: &Input) -> Vec<Disagreement> {
+    let Some(day) = candidates.first().map(|candidate| candidate.day) else {
+        return vec![];
+    };
+
+    [1, 2]
+        .into_iter()
+        .filter_map(|part| {
+            let outcomes = solve_all(candidates, part, &input.contents, &input.ctx);
+
+            if !disagree(&outcomes) {
+                return None;
+            }
+
+            // Reductions must keep the panicking variants, or the minimised input merely
+            // becomes malformed for some of them.
+            let expected_panics = panicked(&outcomes);
+            let reproducer = minimise(&input.contents, |reduced| {
+                let outcomes = solve_all(candidates, part, reduced, &input.ctx);
+                disagree(&outcomes) && panicked(&outcomes) == expected_panics
+            });
+
+            Some(Disagreement {
+                day,
+                part,
+                source: input.source.clone(),
+                outcomes: candidates
+                    .iter()
+                    .map(|candidate| candidate.variant)
+                    .zip(outcomes)
+                    .collect(),
+                reproducer,
+            })
+        })
+        .collect()
+}
+
+/// Compares the candidates of every day on all of the day's [`inputs`].
+///
+/// # Panics
+///
+/// Panics with a report of all disagreements, if there are any.
+pub fn assert_agree(candidates: &[Candidate]) {
+    let mut days: BTreeMap<Day, Vec<&Candidate>> = BTreeMap::new();
+    for candidate in candidates {
+        days.entry(candidate.day).or_default().push(candidate);
+    }
+
+    let disagreements: Vec<Disagreement> = days
+        .iter()
+        .flat_map(|(day, candidates)| {
+            inputs(*day)
+                .iter()
+                .flat_map(|input| check(candidates, input))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    if !disagreements.is_empty() {
+        let report: Vec<String> = disagreements.iter().map(ToString::to_string).collect();
+        panic!("{}", report.join("\n\n"));
+    }
+}
+
+fn solve_all(candidates: &[&Candidate], part: u8, input: &str, ctx: &Context) -> Vec<Outcome> {
+    candidates
+        .iter()
+        .map(|candidate| candidate.solve(part, input, ctx))
+        .collect()
+}
+
+fn disagree(outcomes: &[Outcome]) -> bool {
+    outcomes.windows(2).any(|pair| pair[0] != pair[1])
+}
+
+fn panicked(outcomes: &[Outcome]) -> Vec<bool> {
+    outcomes
+        .iter()
+        .map(|outcome| *outcome == Outcome::Panicked)
+        .collect()
+}
+
+/// Removes chunks of lines from the input as long as `fails` holds, halving the chunk size
+/// whenever no chunk can be removed (a line-based variant of delta debugging).
+pub fn minimise(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let join = |lines: &[&str]| {
+        let mut joined = lines.join("\n");
+        if input.ends_with('\n') {
+            joined.push('\n');
+        }
+        joined
+    };
+
+    let mut lines: Vec<&str> = input.lines().collect();
+    let mut chunk = lines.len().div_ceil(2);
+
+    while chunk > 0 {
+        let mut start = 0;
+        let mut reduced = false;
+
+        while start < lines.len() {
+            let end = (start + chunk).min(lines.len());
+            let candidate: Vec<&str> = [&lines[..start], &lines[end..]].concat();
+
+            if !candidate.is_empty() && fails(&join(&candidate)) {
+                lines = candidate;
+                reduced = true;
+            } else {
+                start += chunk;
+            }
+        }
+
+        if !reduced {
+            chunk /= 2;
+        }
+    }
+
+    join(&lines)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Answer, Candidate, Input, Outcome, check, minimise};
+    use crate::{day, template::Context};
+
+    fn sum(input: &str, _: &Context) -> Answer {
+        let sum: u64 = input.lines().map(|line| line.parse::<u64>().unwrap()).sum();
+        Ok(Some(sum.to_string()))
+    }
+
+    fn sum_skipping_sevens(input: &str, _: &Context) -> Answer {
+        let sum: u64 = input
+            .lines()
+            .map(|line| line.parse::<u64>().unwrap())
+            .filter(|n| *n != 7)
+            .sum();
+        Ok(Some(sum.to_string()))
+    }
+
+    fn candidate(
+        variant: Option<&'static str>,
+        part_one: fn(&str, &Context) -> Answer,
+    ) -> Candidate {
+        Candidate {
+            day: day!(1),
+            variant,
+            part_one,
+            part_two: |_, _| Ok(None),
+        }
+    }
+
+    #[test]
+    fn minimises_to_failing_lines() {
+        let input = "1\n2\n7\n4\n5\n6\n";
+        let reduced = minimise(input, |input| input.lines().any(|line| line == "7"));
+        assert_eq!(reduced, "7\n");
+    }
+
+    #[test]
+    fn reports_disagreements_with_reproducer() {
+        let main = candidate(None, sum);
+        let fast = candidate(Some("fast"), sum_skipping_sevens);
+        let input = Input {
+            source: "generated".into(),
+            contents: "1\n2\n7\n4\n".into(),
+            ctx: Context::real(),
+        };
+
+        let disagreements = check(&[&main, &fast], &input);
+        assert_eq!(disagreements.len(), 1);
+
+        let disagreement = &disagreements[0];
+        assert_eq!(disagreement.part, 1);
+        assert_eq!(disagreement.reproducer, "7\n");
+        assert_eq!(
+            disagreement.outcomes,
+            vec![
+                (None, Outcome::Answer(Some("14".into()))),
+                (Some("fast"), Outcome::Answer(Some("7".into())))
+            ]
+        );
+    }
+
+    #[test]
+    fn accepts_agreeing_variants() {
+        let main = candidate(None, sum);
+        let fast = candidate(Some("fast"), sum_skipping_sevens);
+        let input = Input {
+            source: "generated".into(),
+            contents: "1\n2\n3\n".into(),
+            ctx: Context::real(),
+        };
+
+        assert!(check(&[&main, &fast], &input).is_empty());
+    }
+}
@@ -0,0 +1,298 @@
+//! The machine that benchmarks run on, and pinning them to one of its CPUs.
+//!
+//! `cargo time` records the environment in `timings.json`, and generates the "benchmarked
+//! on" note of the readme from it. The compiler and its flags are captured by `build.rs`,
+//! as the solutions are built with the same ones as the runner.
+use std::{collections::HashMap, env, fs, process::Command, thread};
+
+use tinyjson::JsonValue;
+
+/// Describes the machine and toolchain of a benchmark. Details that could not be detected
+/// are `None`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Environment {
+    pub cpu_model: Option<String>,
+    /// The frequency scaling governor of the benchmarked CPU, e.g. `performance`.
+    pub governor: Option<String>,
+    /// The number of CPUs available to the process.
+    pub cores: Option<usize>,
+    pub os: String,
+    /// The output of `rustc --version`.
+    pub rustc: Option<String>,
+    /// The `target-cpu` and `target-feature` codegen flags the solutions were built with.
+    pub target_flags: Vec<String>,
+    /// The CPU the benchmarks were pinned to, see [`pin_to_cpu`].
+    pub pinned_cpu: Option<usize>,
+}
+
+impl Environment {
+    /// Detects the environment of benchmarks pinned to `pinned_cpu`, if any.
+    pub fn detect(pinned_cpu: Option<usize>) -> Self {
+        let target_flags = option_env!("AOC_TARGET_FLAGS")
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(Into::into)
+            .collect();
+
+        Environment {
+            cpu_model: cpu_model(),
+            governor: governor(pinned_cpu.unwrap_or(0)),
+            cores: thread::available_parallelism().ok().map(Into::into),
+            os: env::consts::OS.into(),
+            rustc: option_env!("AOC_RUSTC_VERSION").map(Into::into),
+            target_flags,
+            pinned_cpu,
+        }
+    }
+
+    /// Formats the environment for the readme, e.g. `Rust benchmarked on an AMD Ryzen 9
+    /// 9950X (16 cores, performance governor) using linux with rustc 1.91.0 and target-cpu=native`.
+    pub fn note(&self) -> String {
+        let cpu = self.cpu_model.as_deref().unwrap_or("an unknown CPU");
+
+        let details: Vec<String> = [
+            self.cores.map(|cores| match cores {
+                1 => "1 core".into(),
+                n => format!("{n} cores"),
+            }),
+            self.governor
+                .as_ref()
+                .map(|governor| format!("{governor} governor")),
+            self.pinned_cpu.map(|cpu| format!("pinned to CPU {cpu}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let mut note = format!("Rust benchmarked on {cpu}");
+
+        if !details.is_empty() {
+            note += &format!(" ({})", details.join(", "));
+        }
+
+        note += &format!(" using {}", self.os);
+
+        if let Some(rustc) = &self.rustc {
+            // NOTE: the commit hash and date of `rustc --version` are left out.
+            let version = rustc.split(" (").next().unwrap_or(rustc);
+            note += &format!(" with {version}");
+        }
+
+        if !self.target_flags.is_empty() {
+            note += &format!(" and {}", self.target_flags.join(" "));
+        }
+
+        note
+    }
+}
+
+fn cpu_model() -> Option<String> {
+    let model = fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|cpuinfo| {
+            cpuinfo
+                .lines()
+                .find(|line| line.starts_with("model name"))
+                .and_then(|line| line.split_once(':'))
+                .map(|(_, model)| model.trim().to_string())
+        })
+        .or_else(|| {
+            // NOTE: macOS has no `/proc`.
+            let output = Command::new("sysctl")
+                .args(["-n", "machdep.cpu.brand_string"])
+                .output()
+                .ok()?;
+            output
+                .status
+                .success()
+                .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        });
+
+    model.filter(|model| !model.is_empty())
+}
+
+fn governor(cpu: usize) -> Option<String> {
+    fs::read_to_string(format!(
+        "/sys/devices/system/cpu/cpu{cpu}/cpufreq/scaling_governor"
+    ))
+    .ok()
+    .map(|governor| governor.trim().to_string())
+}
+
+/// Pins the calling thread to `cpu`, so that benchmarks are not migrated between CPUs.
+///
+/// # Errors
+///
+/// Returns the reason if the thread could not be pinned, e.g. when the CPU does not exist
+/// or the system is not Linux.
+#[cfg(target_os = "linux")]
+pub fn pin_to_cpu(cpu: usize) -> Result<(), String> {
+    if cpu >= libc::CPU_SETSIZE as usize {
+        return Err(format!("CPU {cpu} does not exist"));
+    }
+
+    // SAFETY: `cpu_set_t` is a plain bit set, for which all zeroes is the empty set.
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    // SAFETY: `cpu` was checked to be within the set.
+    unsafe { libc::CPU_SET(cpu, &mut set) };
+
+    // SAFETY: `set` is a valid `cpu_set_t` of the given size, and 0 selects the calling thread.
+    let result = unsafe {
+        libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &raw const set)
+    };
+
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(format!(
+            "could not pin to CPU {cpu}: {}",
+            std::io::Error::last_os_error()
+        ))
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pin_to_cpu(_: usize) -> Result<(), String> {
+    Err("pinning to a CPU is only supported on Linux".into())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Environment> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Environment) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        let string = |s: &Option<String>| match s {
+            Some(s) => JsonValue::String(s.clone()),
+            None => JsonValue::Null,
+        };
+        let number = |n: Option<usize>| match n {
+            Some(n) => JsonValue::Number(n as f64),
+            None => JsonValue::Null,
+        };
+
+        map.insert("cpu_model".into(), string(&value.cpu_model));
+        map.insert("governor".into(), string(&value.governor));
+        map.insert("cores".into(), number(value.cores));
+        map.insert("os".into(), JsonValue::String(value.os.clone()));
+        map.insert("rustc".into(), string(&value.rustc));
+        map.insert(
+            "target_flags".into(),
+            JsonValue::Array(
+                value
+                    .target_flags
+                    .iter()
+                    .map(|flag| JsonValue::String(flag.clone()))
+                    .collect(),
+            ),
+        );
+        map.insert("pinned_cpu".into(), number(value.pinned_cpu));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Environment {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected environment to be a JSON object.")?;
+
+        let string = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or(format!("Expected environment.{key} to be null or string.")),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => v
+                .get::<f64>()
+                .map(|&n| Some(n as usize))
+                .ok_or(format!("Expected environment.{key} to be null or number.")),
+        };
+
+        let target_flags = match json.get("target_flags") {
+            None => vec![],
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .and_then(|flags| {
+                    flags
+                        .iter()
+                        .map(|flag| flag.get::<String>().cloned())
+                        .collect()
+                })
+                .ok_or("Expected environment.target_flags to be an array of strings.")?,
+        };
+
+        Ok(Environment {
+            cpu_model: string("cpu_model")?,
+            governor: string("governor")?,
+            cores: number("cores")?,
+            os: string("os")?.ok_or("Expected environment.os to be a string.")?,
+            rustc: string("rustc")?,
+            target_flags,
+            pinned_cpu: number("pinned_cpu")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::Environment;
+
+    fn environment() -> Environment {
+        Environment {
+            cpu_model: Some("AMD Ryzen 9 9950X 16-Core Processor".into()),
+            governor: Some("performance".into()),
+            cores: Some(32),
+            os: "linux".into(),
+            rustc: Some("rustc 1.91.0 (f8297e351 2025-10-28)".into()),
+            target_flags: vec!["target-cpu=native".into()],
+            pinned_cpu: Some(2),
+        }
+    }
+
+    #[test]
+    fn formats_note() {
+        assert_eq!(
+            environment().note(),
+            "Rust benchmarked on AMD Ryzen 9 9950X 16-Core Processor (32 cores, performance governor, pinned to CPU 2) using linux with rustc 1.91.0 and target-cpu=native"
+        );
+
+        let unknown = Environment {
+            os: "macos".into(),
+            ..Environment::default()
+        };
+        assert_eq!(
+            unknown.note(),
+            "Rust benchmarked on an unknown CPU using macos"
+        );
+    }
+
+    #[test]
+    fn round_trips_environment() {
+        let environment = environment();
+        let json = JsonValue::from(&environment);
+        assert_eq!(Environment::try_from(&json), Ok(environment));
+    }
+
+    #[test]
+    fn detects_environment() {
+        let environment = Environment::detect(None);
+        assert_eq!(environment.os, std::env::consts::OS);
+        assert_eq!(environment.pinned_cpu, None);
+    }
+}
@@ -0,0 +1,134 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The event used when `AOC_YEAR` is not set at compile time.
+const DEFAULT_YEAR: u16 = 2025;
+
+/// The first year with an Advent of Code event.
+const FIRST_YEAR: u16 = 2015;
+
+/// From this year on, events only have 12 puzzles.
+const SHORT_EVENT_YEAR: u16 = 2025;
+
+/// An Advent of Code event, identified by its year.
+///
+/// The current event is configured by the `AOC_YEAR` environment variable
+/// (see `.cargo/config.toml`), read at compile time.
+///
+/// ```
+/// # use advent_of_code::template::Event;
+/// let event = Event::new(2024).unwrap();
+/// assert_eq!(event.days(), 25);
+/// assert_eq!(Event::new(2025).unwrap().days(), 12);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Event(u16);
+
+impl Event {
+    /// Creates an [`Event`] if an Advent of Code took place in the given year,
+    /// returns [`None`] otherwise.
+    pub const fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Returns the event configured by `AOC_YEAR`.
+    pub const fn current() -> Self {
+        match Self::new(parse_year(option_env!("AOC_YEAR"))) {
+            Some(event) => event,
+            None => Self(DEFAULT_YEAR),
+        }
+    }
+
+    /// The year of the event.
+    pub const fn year(self) -> u16 {
+        self.0
+    }
+
+    /// The number of puzzles in the event.
+    pub const fn days(self) -> u8 {
+        if self.0 >= SHORT_EVENT_YEAR { 12 } else { 25 }
+    }
+}
+
+/// Parses a year in a const context, falling back to [`DEFAULT_YEAR`].
+const fn parse_year(value: Option<&str>) -> u16 {
+    let Some(value) = value else {
+        return DEFAULT_YEAR;
+    };
+
+    let bytes = value.as_bytes();
+
+    if bytes.is_empty() || bytes.len() > 4 {
+        return DEFAULT_YEAR;
+    }
+
+    let mut year = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            return DEFAULT_YEAR;
+        }
+        year = year * 10 + (bytes[i] - b'0') as u16;
+        i += 1;
+    }
+
+    year
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Event {
+    type Err = EventFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| EventFromStrError)?;
+        Self::new(year).ok_or(EventFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing an [`Event`].
+#[derive(Debug)]
+pub struct EventFromStrError;
+
+impl Error for EventFromStrError {}
+
+impl Display for EventFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a year from {FIRST_YEAR} onwards")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{DEFAULT_YEAR, Event, parse_year};
+
+    #[test]
+    fn knows_event_lengths() {
+        assert_eq!(Event::new(2015).unwrap().days(), 25);
+        assert_eq!(Event::new(2024).unwrap().days(), 25);
+        assert_eq!(Event::new(2025).unwrap().days(), 12);
+        assert_eq!(Event::new(2014), None);
+    }
+
+    #[test]
+    fn parses_years_in_const_context() {
+        const YEAR: u16 = parse_year(Some("2023"));
+        assert_eq!(YEAR, 2023);
+        assert_eq!(parse_year(None), DEFAULT_YEAR);
+        assert_eq!(parse_year(Some("20x5")), DEFAULT_YEAR);
+        assert_eq!(parse_year(Some("")), DEFAULT_YEAR);
+    }
+}
@@ -0,0 +1,412 @@
+//! Exports of benchmark timings for spreadsheets and for comparing machines.
+//!
+//! `cargo time --export <format>` writes one row per benchmarked part to
+//! `data/export/timings.{csv,json,md,html}`. Unlike `data/timings.json`, which stores
+//! formatted durations, every duration is exported in nanoseconds.
+use std::{collections::HashMap, fmt::Write, fs, io, path::PathBuf, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    Day, Event,
+    alloc::AllocStats,
+    layout,
+    report::{self, Status},
+    stats::Stats,
+    timings::Timings,
+};
+
+const EXPORT_DIR_NAME: &str = "export";
+
+const COLUMNS: [&str; 16] = [
+    "year",
+    "day",
+    "variant",
+    "part",
+    "status",
+    "samples",
+    "min_nanos",
+    "median_nanos",
+    "mean_nanos",
+    "p95_nanos",
+    "std_dev_nanos",
+    "outliers",
+    "allocations",
+    "allocated_bytes",
+    "peak_bytes",
+    "cpu",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+    Markdown,
+    Html,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Json => "json",
+            Format::Markdown => "md",
+            Format::Html => "html",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            "markdown" | "md" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            _ => Err(format!(
+                "unknown export format `{s}`, expected csv, json, markdown or html"
+            )),
+        }
+    }
+}
+
+/// The timing of a single part. Parts that failed or breached a limit have no stats.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Row {
+    pub day: Day,
+    pub variant: Option<String>,
+    /// The part, [`report::PARSE`] for the parse phase of two-phase solutions.
+    pub part: u8,
+    pub status: Status,
+    pub stats: Option<Stats>,
+    pub alloc: Option<AllocStats>,
+    /// The CPU model of the machine, see [`Environment`](crate::template::environment::Environment).
+    pub cpu: Option<String>,
+}
+
+impl Row {
+    /// The cells of the row, in the order of [`COLUMNS`]. Missing values are empty.
+    fn cells(&self) -> [String; 16] {
+        let stat = |f: fn(&Stats) -> f64| self.stats.as_ref().map(f).map(|n| n.to_string());
+        let alloc = |f: fn(&AllocStats) -> u64| self.alloc.as_ref().map(f).map(|n| n.to_string());
+
+        [
+            Some(self.day.event().to_string()),
+            Some(self.day.into_inner().to_string()),
+            self.variant.clone(),
+            Some(self.part.to_string()),
+            Some(self.status.as_str().into()),
+            self.stats.as_ref().map(|s| s.samples.to_string()),
+            stat(|s| s.min),
+            stat(|s| s.median),
+            stat(|s| s.mean),
+            stat(|s| s.p95),
+            stat(|s| s.std_dev),
+            self.stats.as_ref().map(|s| s.outliers.to_string()),
+            alloc(|a| a.allocations),
+            alloc(|a| a.bytes),
+            alloc(|a| a.peak_bytes),
+            self.cpu.clone(),
+        ]
+        .map(Option::unwrap_or_default)
+    }
+}
+
+/// Lists the parts of all timings that were benched, failed or breached a limit.
+pub fn rows(timings: &Timings) -> Vec<Row> {
+    let cpu = timings
+        .environment
+        .as_ref()
+        .and_then(|environment| environment.cpu_model.clone());
+
+    let mut rows = vec![];
+
+    for timing in &timings.data {
+        for (part, time, stats, alloc) in [
+            (
+                report::PARSE,
+                &timing.parse,
+                &timing.parse_stats,
+                timing.parse_alloc,
+            ),
+            (1, &timing.part_1, &timing.part_1_stats, timing.part_1_alloc),
+            (2, &timing.part_2, &timing.part_2_stats, timing.part_2_alloc),
+        ] {
+            let status = if timing.failed.contains(&part) {
+                Status::Failed
+            } else if timing.timed_out.contains(&part) {
+                Status::Timeout
+            } else if timing.out_of_memory.contains(&part) {
+                Status::OutOfMemory
+            } else if time.is_some() {
+                Status::Solved
+            } else {
+                continue;
+            };
+
+            rows.push(Row {
+                day: timing.day,
+                variant: timing.variant.clone(),
+                part,
+                status,
+                stats: stats.clone(),
+                alloc,
+                cpu: cpu.clone(),
+            });
+        }
+    }
+
+    rows
+}
+
+/// Renders the rows of the timings in the given format.
+pub fn render(timings: &Timings, format: Format) -> String {
+    let rows = rows(timings);
+
+    match format {
+        Format::Csv => to_csv(&rows),
+        Format::Json => to_json(timings, &rows),
+        Format::Markdown => to_markdown(&rows),
+        Format::Html => to_html(&rows),
+    }
+}
+
+/// Writes the export to `data/export/timings.{extension}`, returning its path.
+pub fn store_file(timings: &Timings, format: Format, event: Event) -> io::Result<PathBuf> {
+    let dir = layout::data_dir(event).join(EXPORT_DIR_NAME);
+    fs::create_dir_all(&dir)?;
+
+    let path = dir.join(format!("timings.{}", format.extension()));
+    fs::write(&path, render(timings, format))?;
+    Ok(path)
+}
+
+fn to_csv(rows: &[Row]) -> String {
+    // NOTE: CPU models may contain commas, they are quoted.
+    let field = |cell: String| {
+        if cell.contains([',', '"', '\n']) {
+            format!("\"{}\"", cell.replace('"', "\"\""))
+        } else {
+            cell
+        }
+    };
+
+    let mut lines = vec![COLUMNS.join(",")];
+
+    for row in rows {
+        lines.push(row.cells().map(field).join(","));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn to_json(timings: &Timings, rows: &[Row]) -> String {
+    let number = |n: Option<f64>| n.map_or(JsonValue::Null, JsonValue::Number);
+
+    let rows = rows
+        .iter()
+        .map(|row| {
+            let stat = |f: fn(&Stats) -> f64| number(row.stats.as_ref().map(f));
+            let alloc = |f: fn(&AllocStats) -> u64| number(row.alloc.as_ref().map(|a| f(a) as f64));
+
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+            map.insert(
+                "year".into(),
+                JsonValue::Number(f64::from(row.day.event().year())),
+            );
+            map.insert(
+                "day".into(),
+                JsonValue::Number(f64::from(row.day.into_inner())),
+            );
+            map.insert(
+                "variant".into(),
+                row.variant
+                    .clone()
+                    .map_or(JsonValue::Null, JsonValue::String),
+            );
+            map.insert("part".into(), JsonValue::Number(f64::from(row.part)));
+            map.insert(
+                "status".into(),
+                JsonValue::String(row.status.as_str().into()),
+            );
+            map.insert(
+                "samples".into(),
+                number(row.stats.as_ref().map(|s| s.samples as f64)),
+            );
+            map.insert("min_nanos".into(), stat(|s| s.min));
+            map.insert("median_nanos".into(), stat(|s| s.median));
+            map.insert("mean_nanos".into(), stat(|s| s.mean));
+            map.insert("p95_nanos".into(), stat(|s| s.p95));
+            map.insert("std_dev_nanos".into(), stat(|s| s.std_dev));
+            map.insert(
+                "outliers".into(),
+                number(row.stats.as_ref().map(|s| s.outliers as f64)),
+            );
+            map.insert("allocations".into(), alloc(|a| a.allocations));
+            map.insert("allocated_bytes".into(), alloc(|a| a.bytes));
+            map.insert("peak_bytes".into(), alloc(|a| a.peak_bytes));
+
+            JsonValue::Object(map)
+        })
+        .collect();
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert(
+        "environment".into(),
+        timings
+            .environment
+            .as_ref()
+            .map_or(JsonValue::Null, JsonValue::from),
+    );
+    map.insert("rows".into(), JsonValue::Array(rows));
+
+    // NOTE: the JSON values are built from numbers and strings only, which always format.
+    JsonValue::Object(map).format().unwrap() + "\n"
+}
+
+fn to_markdown(rows: &[Row]) -> String {
+    let mut lines = vec![
+        format!("| {} |", COLUMNS.join(" | ")),
+        format!("| {} |", ["---:"; COLUMNS.len()].join(" | ")),
+    ];
+
+    for row in rows {
+        let cells = row.cells().map(|cell| cell.replace('|', "\\|"));
+        lines.push(format!("| {} |", cells.join(" | ")));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+fn to_html(rows: &[Row]) -> String {
+    let escape = |cell: &str| {
+        cell.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    };
+
+    let mut html = String::from("<table>\n  <thead>\n    <tr>");
+    for column in COLUMNS {
+        write!(html, "<th>{column}</th>").unwrap();
+    }
+    html += "</tr>\n  </thead>\n  <tbody>\n";
+
+    for row in rows {
+        html += "    <tr>";
+        for cell in row.cells() {
+            write!(html, "<td>{}</td>", escape(&cell)).unwrap();
+        }
+        html += "</tr>\n";
+    }
+
+    html + "  </tbody>\n</table>\n"
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{Format, render, rows};
+    use crate::{
+        day,
+        template::{
+            alloc::AllocStats,
+            environment::Environment,
+            report::Status,
+            stats::Stats,
+            timings::{Timing, Timings, mock::mock_timing},
+        },
+    };
+
+    fn timings() -> Timings {
+        Timings {
+            data: vec![Timing {
+                variant: Some("fast".into()),
+                parse: Some("1.0ms".into()),
+                part_1_stats: Some(Stats {
+                    samples: 10,
+                    min: 1900.0,
+                    median: 2000.0,
+                    mean: 2012.5,
+                    p95: 2100.0,
+                    std_dev: 50.0,
+                    outliers: 1,
+                }),
+                part_1_alloc: Some(AllocStats::default()),
+                timed_out: vec![2],
+                ..mock_timing(day!(8), Some("2.0µs"), None, 1_002_000.0)
+            }],
+            history: vec![],
+            environment: Some(Environment {
+                cpu_model: Some("Xeon, 2 sockets".into()),
+                os: "linux".into(),
+                ..Environment::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn lists_parts() {
+        let rows = rows(&timings());
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows.iter().map(|r| (r.part, r.status)).collect::<Vec<_>>(),
+            vec![
+                (0, Status::Solved),
+                (1, Status::Solved),
+                (2, Status::Timeout)
+            ]
+        );
+    }
+
+    #[test]
+    fn exports_csv() {
+        let csv = render(&timings(), Format::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[2],
+            "2025,8,fast,1,solved,10,1900,2000,2012.5,2100,50,1,0,0,0,\"Xeon, 2 sockets\""
+        );
+        assert_eq!(
+            lines[3],
+            "2025,8,fast,2,timeout,,,,,,,,,,,\"Xeon, 2 sockets\""
+        );
+    }
+
+    #[test]
+    fn exports_json() {
+        let json: JsonValue = render(&timings(), Format::Json).parse().unwrap();
+        let rows: &Vec<JsonValue> = json["rows"].get().unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1]["median_nanos"], JsonValue::Number(2000.0));
+        assert_eq!(rows[2]["median_nanos"], JsonValue::Null);
+        assert_eq!(
+            json["environment"]["cpu_model"],
+            JsonValue::String("Xeon, 2 sockets".into())
+        );
+    }
+
+    #[test]
+    fn exports_tables() {
+        let markdown = render(&timings(), Format::Markdown);
+        assert_eq!(markdown.lines().count(), 5);
+        assert!(markdown.contains("| 2025 | 8 | fast | 1 | solved | 10 | 1900 | 2000 |"));
+
+        let html = render(&timings(), Format::Html);
+        assert_eq!(html.matches("<tr>").count(), 4);
+        assert!(html.contains("<td>timeout</td>"));
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("md".parse(), Ok(Format::Markdown));
+        assert_eq!("html".parse(), Ok(Format::Html));
+        assert!("xlsx".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,186 @@
+//! Locations of solution binaries and data files.
+//!
+//! The current event (see [`Event::current`]) uses the single-year layout, with
+//! data in `data/` and binaries named after the day (e.g. `src/bin/01.rs`).
+//! Other events keep their data in `data/{year}/` and use year-qualified binary
+//! names (e.g. `src/bin/2024_01.rs`).
+//!
+//! Variants of a solution append their name to the binary name of the day, e.g.
+//! `src/bin/08_fast.rs` for the variant `fast` of day 8.
+use std::{fs, path::PathBuf};
+
+use crate::template::{Day, Event};
+
+/// Returns the data directory of an event, relative to the project root.
+pub fn data_dir(event: Event) -> PathBuf {
+    if event == Event::current() {
+        PathBuf::from("data")
+    } else {
+        PathBuf::from("data").join(event.to_string())
+    }
+}
+
+/// Returns the path of a day's text file in a data folder, e.g. `data/inputs/01.txt`.
+pub fn data_file(folder: &str, day: Day) -> PathBuf {
+    data_dir(day.event())
+        .join(folder)
+        .join(format!("{day}.txt"))
+}
+
+/// Returns the path of a generated input of a day, e.g. `data/generated/08-1000-42.txt`.
+pub fn generated_file(day: Day, size: usize, seed: u64) -> PathBuf {
+    data_dir(day.event())
+        .join("generated")
+        .join(format!("{day}-{size}-{seed}.txt"))
+}
+
+/// Returns the generated inputs of a day, sorted by size and seed.
+/// Their names start with the day and size, e.g. `08-1000-42.txt` (see [`generated_file`]).
+pub fn generated_files(day: Day) -> Vec<(usize, PathBuf)> {
+    let prefix = format!("{day}-");
+    let Ok(entries) = fs::read_dir(data_dir(day.event()).join("generated")) else {
+        return vec![];
+    };
+
+    let mut files: Vec<(usize, PathBuf)> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?;
+            let rest = name.strip_prefix(&prefix)?.strip_suffix(".txt")?;
+            let size = rest.split('-').next()?.parse().ok()?;
+            Some((size, path))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Returns the path of an event-wide JSON file, e.g. `data/timings.json`.
+pub fn event_file(event: Event, name: &str) -> PathBuf {
+    data_dir(event).join(format!("{name}.json"))
+}
+
+/// Returns the path of a day's puzzle description.
+pub fn puzzle_file(day: Day) -> PathBuf {
+    data_dir(day.event())
+        .join("puzzles")
+        .join(format!("{day}.md"))
+}
+
+/// Returns the cargo binary name of a day's solution.
+pub fn bin_name(day: Day) -> String {
+    if day.event() == Event::current() {
+        day.to_string()
+    } else {
+        format!("{}_{day}", day.event())
+    }
+}
+
+/// Returns the source path of a day's solution.
+pub fn bin_path(day: Day) -> String {
+    format!("./src/bin/{}.rs", bin_name(day))
+}
+
+/// Returns the cargo binary name of a variant of a day's solution.
+pub fn variant_bin_name(day: Day, variant: Option<&str>) -> String {
+    match variant {
+        Some(variant) => format!("{}_{variant}", bin_name(day)),
+        None => bin_name(day),
+    }
+}
+
+/// Returns the source path of a variant of a day's solution.
+pub fn variant_bin_path(day: Day, variant: Option<&str>) -> String {
+    format!("./src/bin/{}.rs", variant_bin_name(day, variant))
+}
+
+/// Returns the names of all variants of a day's solution found in `src/bin/`, sorted by name.
+/// The main solution, if scaffolded, comes first as `None`.
+pub fn variants(day: Day) -> Vec<Option<String>> {
+    let prefix = format!("{}_", bin_name(day));
+
+    let mut variants: Vec<String> = fs::read_dir("./src/bin")
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter_map(|file| {
+            let variant = file.strip_suffix(".rs")?.strip_prefix(&prefix)?;
+            is_variant_name(variant).then(|| variant.to_string())
+        })
+        .collect();
+    variants.sort();
+
+    let main = fs::exists(bin_path(day)).unwrap_or(false).then_some(None);
+    main.into_iter()
+        .chain(variants.into_iter().map(Some))
+        .collect()
+}
+
+/// Variant names start with a letter, so that they can't be mistaken for a day of another event.
+pub fn is_variant_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{
+        bin_name, bin_path, data_file, event_file, is_variant_name, variant_bin_name,
+        variant_bin_path,
+    };
+    use crate::{day, template::Event};
+
+    #[test]
+    fn uses_single_year_layout_for_current_event() {
+        assert_eq!(bin_name(day!(1)), "01");
+        assert_eq!(bin_path(day!(1)), "./src/bin/01.rs");
+        assert_eq!(
+            data_file("inputs", day!(1)),
+            PathBuf::from("data/inputs/01.txt")
+        );
+        assert_eq!(
+            event_file(Event::current(), "timings"),
+            PathBuf::from("data/timings.json")
+        );
+    }
+
+    #[test]
+    fn qualifies_other_events_with_year() {
+        let day = day!(25, year = 2015);
+        assert_eq!(bin_name(day), "2015_25");
+        assert_eq!(bin_path(day), "./src/bin/2015_25.rs");
+        assert_eq!(
+            data_file("examples", day),
+            PathBuf::from("data/2015/examples/25.txt")
+        );
+        assert_eq!(
+            event_file(day.event(), "answers"),
+            PathBuf::from("data/2015/answers.json")
+        );
+    }
+
+    #[test]
+    fn appends_variant_names() {
+        assert_eq!(variant_bin_name(day!(8), None), "08");
+        assert_eq!(variant_bin_name(day!(8), Some("fast")), "08_fast");
+        assert_eq!(
+            variant_bin_path(day!(3, year = 2017), Some("simd")),
+            "./src/bin/2017_03_simd.rs"
+        );
+    }
+
+    #[test]
+    fn validates_variant_names() {
+        assert!(is_variant_name("fast"));
+        assert!(is_variant_name("fast_v2"));
+        assert!(!is_variant_name("03"));
+        assert!(!is_variant_name("Fast"));
+        assert!(!is_variant_name(""));
+    }
+}
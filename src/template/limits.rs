@@ -0,0 +1,501 @@
+//! Wall-clock and memory limits of solution parts.
+//!
+//! Limits are read from `data/limits.json` by the solution binaries themselves, so that they
+//! apply per day even when `cargo all` runs all days in one process:
+//!
+//! ```json
+//! {
+//!   "default": { "timeout": 60, "memory": 4096 },
+//!   "data": [{ "day": "10", "timeout": 300 }]
+//! }
+//! ```
+//!
+//! Timeouts are in seconds and apply to every run of a part, memory limits are in MiB of
+//! resident memory the part adds to the process. [`TIMEOUT_ENV`] and [`MEMORY_LIMIT_ENV`]
+//! override the file for all days, they are set by the `--timeout` and `--memory-limit` flags
+//! of `cargo all`, `cargo verify` and `cargo time`.
+//!
+//! While a part runs, including all runs of a benchmark, a watchdog thread polls its runtime and
+//! the resident memory of the process. It records a part that exceeds a limit with the status
+//! `timeout` or `oom` and ends the process. Memory limits are only enforced on Linux, and a part
+//! that allocates faster than the watchdog polls can exhaust the memory of the system first.
+use std::{
+    collections::HashMap,
+    env, fs,
+    process::{self, Command},
+    str::FromStr,
+    sync::{
+        Mutex, Once, OnceLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    thread::{self, Thread},
+    time::{Duration, Instant},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    ANSI_ITALIC, ANSI_RESET, Day, Event,
+    day::day_from_json,
+    layout,
+    report::{self, PartReport, Status},
+};
+
+/// Environment variable that overrides the timeout of all days, in seconds.
+pub const TIMEOUT_ENV: &str = "AOC_TIMEOUT";
+
+/// Environment variable that overrides the memory limit of all days, in MiB.
+pub const MEMORY_LIMIT_ENV: &str = "AOC_MEMORY_LIMIT";
+
+static LIMITS_FILE_NAME: &str = "limits";
+
+/// Exit code of a solution binary that exceeded a limit, like the one of `timeout(1)`.
+pub const EXIT_CODE: i32 = 124;
+
+const MIB: u64 = 1024 * 1024;
+
+/// How often the watchdog polls the resident memory of a part with a memory limit.
+const MEMORY_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How often the watchdog polls the runtime of a part at most.
+const MIN_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// The limits of a part, unlimited if `None`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Limits {
+    pub timeout: Option<Duration>,
+    pub memory_mib: Option<u64>,
+}
+
+impl Limits {
+    pub fn is_unlimited(&self) -> bool {
+        self.timeout.is_none() && self.memory_mib.is_none()
+    }
+
+    /// Fills the limits that are not set from `fallback`.
+    #[must_use]
+    pub fn or(self, fallback: Limits) -> Limits {
+        Limits {
+            timeout: self.timeout.or(fallback.timeout),
+            memory_mib: self.memory_mib.or(fallback.memory_mib),
+        }
+    }
+
+    /// Reads the overrides in [`TIMEOUT_ENV`] and [`MEMORY_LIMIT_ENV`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if either variable is malformed.
+    pub fn from_env() -> Self {
+        let read = |name| env::var(name).ok();
+        parse_env(
+            read(TIMEOUT_ENV).as_deref(),
+            read(MEMORY_LIMIT_ENV).as_deref(),
+        )
+        .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Passes the limits that are set to a solution binary, see [`Limits::from_env`].
+    pub fn apply_to(&self, cmd: &mut Command) {
+        if let Some(timeout) = self.timeout {
+            cmd.env(TIMEOUT_ENV, timeout.as_secs_f64().to_string());
+        }
+        if let Some(memory_mib) = self.memory_mib {
+            cmd.env(MEMORY_LIMIT_ENV, memory_mib.to_string());
+        }
+    }
+
+    /// Returns the limits of a day: the overrides in the environment, otherwise the limits
+    /// of the day in `data/limits.json`, otherwise its defaults.
+    ///
+    /// The limits of the last day are kept, so that the file is read once for all its parts.
+    pub fn for_day(day: Day) -> Self {
+        static LAST: Mutex<Option<(Day, Limits)>> = Mutex::new(None);
+
+        let mut last = LAST.lock().unwrap();
+
+        match *last {
+            Some((last_day, limits)) if last_day == day => limits,
+            _ => {
+                let limits =
+                    Self::from_env().or(LimitsConfig::read_from_file(day.event()).for_day(day));
+                *last = Some((day, limits));
+                limits
+            }
+        }
+    }
+}
+
+fn parse_env(timeout: Option<&str>, memory_mib: Option<&str>) -> Result<Limits, String> {
+    let timeout = timeout
+        .map(|secs| {
+            secs.trim()
+                .parse()
+                .ok()
+                .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                .filter(|timeout| !timeout.is_zero())
+                .ok_or(format!(
+                    "{TIMEOUT_ENV} must be a positive number of seconds, got `{secs}`"
+                ))
+        })
+        .transpose()?;
+
+    let memory_mib = memory_mib
+        .map(|mib| {
+            mib.trim()
+                .parse()
+                .ok()
+                .filter(|&mib| mib > 0)
+                .ok_or(format!(
+                    "{MEMORY_LIMIT_ENV} must be a positive number of MiB, got `{mib}`"
+                ))
+        })
+        .transpose()?;
+
+    Ok(Limits {
+        timeout,
+        memory_mib,
+    })
+}
+
+/// The limits configured in `data/limits.json`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LimitsConfig {
+    /// The limits of days without an entry of their own.
+    pub default: Limits,
+    pub data: Vec<(Day, Limits)>,
+}
+
+impl LimitsConfig {
+    /// Rehydrate limits from a JSON file. If not present or malformed, nothing is limited.
+    pub fn read_from_file(event: Event) -> Self {
+        let path = layout::event_file(event, LIMITS_FILE_NAME);
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => LimitsConfig::try_from(contents).unwrap_or_else(|err| {
+                eprintln!("Ignoring {}: {err}", path.display());
+                LimitsConfig::default()
+            }),
+            Err(_) => LimitsConfig::default(),
+        }
+    }
+
+    /// Returns the limits of a day, falling back to the defaults for the limits it does not set.
+    pub fn for_day(&self, day: Day) -> Limits {
+        self.data
+            .iter()
+            .find(|(d, _)| *d == day)
+            .map_or(self.default, |(_, limits)| limits.or(self.default))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The part the watchdog is watching.
+struct Watched {
+    day: Day,
+    variant: Option<String>,
+    part: u8,
+    limits: Limits,
+    /// The resident memory of the process before the part ran, if it can be measured.
+    base_memory: Option<u64>,
+    /// The value of [`RUNS`] when the current run was first seen by the watchdog.
+    run: u64,
+    run_started: Instant,
+}
+
+static WATCHED: Mutex<Option<Watched>> = Mutex::new(None);
+static WATCHDOG: OnceLock<Thread> = OnceLock::new();
+
+/// Counts the runs of watched parts, see [`Guard::next_run`].
+static RUNS: AtomicU64 = AtomicU64::new(0);
+
+/// Stops watching a part when dropped.
+pub struct Guard;
+
+impl Guard {
+    /// Marks the start of another run of the watched part, whose timeout then starts anew.
+    #[inline]
+    pub fn next_run(&self) {
+        // NOTE: only the thread that runs the part writes, a load and a store are enough.
+        RUNS.store(
+            RUNS.load(Ordering::Relaxed).wrapping_add(1),
+            Ordering::Relaxed,
+        );
+    }
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        *WATCHED.lock().unwrap() = None;
+    }
+}
+
+/// Watches a part until the returned guard is dropped, if its day has any limits.
+pub fn watch(day: Day, variant: Option<&str>, part: u8) -> Option<Guard> {
+    let mut limits = Limits::for_day(day);
+    let base_memory = resident_memory();
+
+    if limits.memory_mib.is_some() && base_memory.is_none() {
+        static UNSUPPORTED: Once = Once::new();
+        UNSUPPORTED.call_once(|| {
+            eprintln!("{ANSI_ITALIC}Memory limits are only enforced on Linux.{ANSI_RESET}");
+        });
+        limits.memory_mib = None;
+    }
+
+    if limits.is_unlimited() {
+        return None;
+    }
+
+    let watched = Watched {
+        day,
+        variant: variant.map(Into::into),
+        part,
+        limits,
+        base_memory,
+        run: RUNS.load(Ordering::Relaxed),
+        run_started: Instant::now(),
+    };
+    *WATCHED.lock().unwrap() = Some(watched);
+
+    // Wakes the watchdog up to pick up the new part.
+    WATCHDOG
+        .get_or_init(|| thread::spawn(watchdog).thread().clone())
+        .unpark();
+
+    Some(Guard)
+}
+
+fn watchdog() {
+    loop {
+        let mut watched = WATCHED.lock().unwrap();
+
+        let Some(part) = watched.as_mut() else {
+            drop(watched);
+            thread::park();
+            continue;
+        };
+
+        let run = RUNS.load(Ordering::Relaxed);
+        if run != part.run {
+            part.run = run;
+            part.run_started = Instant::now();
+        }
+
+        if let Some(timeout) = part.limits.timeout
+            && part.run_started.elapsed() >= timeout
+        {
+            breach(part, Status::Timeout);
+        }
+
+        if let (Some(memory_mib), Some(base)) = (part.limits.memory_mib, part.base_memory)
+            && resident_memory()
+                .is_some_and(|memory| memory.saturating_sub(base) > memory_mib * MIB)
+        {
+            breach(part, Status::OutOfMemory);
+        }
+
+        // NOTE: a run is stopped at most a tenth of its timeout late.
+        let timeout_interval = part
+            .limits
+            .timeout
+            .map(|timeout| (timeout / 10).max(MIN_POLL_INTERVAL));
+        let memory_interval = part.limits.memory_mib.map(|_| MEMORY_POLL_INTERVAL);
+        let interval = match (timeout_interval, memory_interval) {
+            (Some(a), Some(b)) => a.min(b),
+            (a, b) => a.or(b).expect("watching a part without limits"),
+        };
+
+        drop(watched);
+        thread::park_timeout(interval);
+    }
+}
+
+/// Returns the resident memory of the process in bytes, `None` if it cannot be measured.
+#[cfg(target_os = "linux")]
+fn resident_memory() -> Option<u64> {
+    let statm = fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+
+    // SAFETY: sysconf has no preconditions.
+    let page_size = u64::try_from(unsafe { libc::sysconf(libc::_SC_PAGESIZE) }).ok()?;
+
+    Some(pages * page_size)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn resident_memory() -> Option<u64> {
+    None
+}
+
+/// Reports the part as having exceeded its limit and ends the process.
+fn breach(part: &Watched, status: Status) -> ! {
+    let (label, error) = match (status, part.limits.timeout, part.limits.memory_mib) {
+        (Status::Timeout, Some(timeout), _) => {
+            ("TIMEOUT", format!("timed out after {timeout:.1?}"))
+        }
+        (_, _, Some(memory_mib)) => (
+            "OOM",
+            format!("exceeded the memory limit of {memory_mib} MiB"),
+        ),
+        _ => unreachable!("breached a limit that is not set"),
+    };
+
+    // NOTE: the blocked thread may hold the lock of stdout, so the breach goes to stderr.
+    eprintln!("{}: ✖ {label}\n{error}", report::part_label(part.part));
+
+    #[allow(clippy::cast_precision_loss)]
+    let record = PartReport {
+        day: part.day,
+        variant: part.variant.clone(),
+        part: part.part,
+        answer: None,
+        nanos: part.run_started.elapsed().as_nanos() as f64,
+        samples: 1,
+        status,
+        error: Some(error),
+        stats: None,
+        alloc: None,
+        counters: None,
+    };
+
+    if let Err(e) = report::emit(&record) {
+        eprintln!("Failed to write report for part {}: {e}", part.part);
+    }
+
+    process::exit(EXIT_CODE);
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<String> for LimitsConfig {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let default = match json.get("default") {
+            None => Limits::default(),
+            Some(default) => limits_from_json(default, "limits.default")?,
+        };
+
+        let data = match json.get("data") {
+            None => vec![],
+            Some(data) => data
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.data` to be an array.")?
+                .iter()
+                .map(|entry| {
+                    let map = entry
+                        .get::<HashMap<String, JsonValue>>()
+                        .ok_or("Expected limits.data to contain JSON objects.")?;
+                    let day =
+                        day_from_json(map).ok_or("Expected limits.data.day to be a Day struct.")?;
+                    Ok((day, limits_from_json(entry, "limits.data")?))
+                })
+                .collect::<Result<_, String>>()?,
+        };
+
+        Ok(LimitsConfig { default, data })
+    }
+}
+
+fn limits_from_json(value: &JsonValue, path: &str) -> Result<Limits, String> {
+    let json = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or(format!("Expected {path} to be a JSON object."))?;
+
+    let timeout = json
+        .get("timeout")
+        .map(|v| {
+            v.get::<f64>()
+                .and_then(|&secs| Duration::try_from_secs_f64(secs).ok())
+                .filter(|timeout| !timeout.is_zero())
+                .ok_or(format!(
+                    "Expected {path}.timeout to be a positive number of seconds."
+                ))
+        })
+        .transpose()?;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let memory_mib = json
+        .get("memory")
+        .map(|v| {
+            v.get::<f64>()
+                .filter(|&&mib| mib >= 1.0)
+                .map(|&mib| mib as u64)
+                .ok_or(format!(
+                    "Expected {path}.memory to be a positive number of MiB."
+                ))
+        })
+        .transpose()?;
+
+    Ok(Limits {
+        timeout,
+        memory_mib,
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Limits, LimitsConfig, parse_env};
+    use crate::day;
+
+    #[test]
+    fn reads_limits_per_day() {
+        let json = r#"{
+            "default": { "timeout": 60, "memory": 4096 },
+            "data": [{ "day": "10", "timeout": 0.5 }, { "day": "11", "memory": 16 }]
+        }"#;
+        let config = LimitsConfig::try_from(json.to_string()).unwrap();
+
+        let limits = |timeout: f64, memory_mib| Limits {
+            timeout: Some(Duration::from_secs_f64(timeout)),
+            memory_mib: Some(memory_mib),
+        };
+
+        assert_eq!(config.for_day(day!(1)), limits(60.0, 4096));
+        assert_eq!(config.for_day(day!(10)), limits(0.5, 4096));
+        assert_eq!(config.for_day(day!(11)), limits(60.0, 16));
+        assert_eq!(
+            LimitsConfig::try_from("{}".to_string())
+                .unwrap()
+                .for_day(day!(1)),
+            Limits::default()
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_limits() {
+        for json in [
+            r#"{ "default": { "timeout": -1 } }"#,
+            r#"{ "default": { "memory": "1G" } }"#,
+            r#"{ "data": [{ "timeout": 1 }] }"#,
+        ] {
+            assert!(LimitsConfig::try_from(json.to_string()).is_err(), "{json}");
+        }
+    }
+
+    #[test]
+    fn parses_overrides() {
+        assert_eq!(
+            parse_env(Some("1.5"), Some("512")),
+            Ok(Limits {
+                timeout: Some(Duration::from_millis(1500)),
+                memory_mib: Some(512),
+            })
+        );
+        assert_eq!(parse_env(None, None), Ok(Limits::default()));
+        assert!(parse_env(Some("0"), None).is_err());
+        assert!(parse_env(None, Some("lots")).is_err());
+    }
+}
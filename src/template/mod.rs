@@ -1,25 +1,55 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod alloc;
+pub mod aoc_client;
 pub mod commands;
+pub mod counters;
+pub mod differential;
+pub mod environment;
+pub mod export;
+pub mod layout;
+pub mod limits;
+pub mod registry;
+pub mod report;
+pub mod run_multi;
 pub mod runner;
+pub mod scaling;
+pub mod stats;
 
+pub use context::*;
 pub use day::*;
+pub use event::*;
 
+mod answers;
+mod clock;
+mod context;
 mod day;
+mod event;
 mod readme_benchmarks;
-mod run_multi;
+mod submissions;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Environment variable that points a solution to another input file than its puzzle input.
+pub const INPUT_FILE_ENV: &str = "AOC_INPUT_FILE";
+
+/// Reads the input of a solution: the file in [`INPUT_FILE_ENV`] if set, the puzzle input otherwise.
+#[must_use]
+pub fn read_input(day: Day) -> String {
+    match env::var_os(INPUT_FILE_ENV) {
+        Some(path) => fs::read_to_string(path).expect("could not open input file"),
+        None => read_file("inputs", day),
+    }
+}
+
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(layout::data_file(folder, day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
@@ -29,40 +59,112 @@ pub fn read_file(folder: &str, day: Day) -> String {
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(layout::data_dir(day.event()))
         .join(folder)
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `VARIANT` and sets up the input and runner for each part.
+///
+/// The optional, last parameter (1 or 2) allows you to only run a single part of the solution.
+/// Solutions of another event than the current one specify their year, e.g. `solution!(1, year = 2024)`.
+/// Alternative implementations of a day name their variant, e.g. `solution!(8, variant = "fast")`
+/// in `src/bin/08_fast.rs`.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Two-phase solutions name the function that parses the input once for both parts, e.g.
+/// `solution!(8, parse = parse)`. It returns a `Result` of the parsed input, and the parts take a
+/// reference to the parsed input instead of the input. Parsing is timed and reported on its own.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr $(, year = $year:expr)? $(, variant = $variant:literal)? $(, parse = $parse:ident)?, 1) => {
+        $crate::solution!(
+            @impl $crate::day!($day $(, year = $year)?),
+            $crate::solution!(@variant $($variant)?),
+            [$($parse)?],
+            [part_one, 1]
+        );
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($day:expr $(, year = $year:expr)? $(, variant = $variant:literal)? $(, parse = $parse:ident)?, 2) => {
+        $crate::solution!(
+            @impl $crate::day!($day $(, year = $year)?),
+            $crate::solution!(@variant $($variant)?),
+            [$($parse)?],
+            [part_two, 2]
+        );
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($day:expr $(, year = $year:expr)? $(, variant = $variant:literal)? $(, parse = $parse:ident)?) => {
+        $crate::solution!(
+            @impl $crate::day!($day $(, year = $year)?),
+            $crate::solution!(@variant $($variant)?),
+            [$($parse)?],
+            [part_one, 1] [part_two, 2]
+        );
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@variant) => {
+        None
+    };
+    (@variant $variant:literal) => {
+        Some($variant)
+    };
+
+    (@run [], $input:expr, $ctx:expr, $( [$func:expr, $part:expr] )*) => {
+        $( $crate::template::runner::run_part($func, $input, $ctx, DAY, VARIANT, $part); )*
+    };
+    (@run [$parse:ident], $input:expr, $ctx:expr, $( [$func:expr, $part:expr] )*) => {
+        let parsed = $crate::template::runner::run_parse($parse, $input, $ctx, DAY, VARIANT);
+        $(
+            $crate::template::runner::run_parsed_part(
+                $func, parsed.as_ref(), $ctx, DAY, VARIANT, $part
+            );
+        )*
+    };
+
+    (@answer [], $input:expr, $ctx:expr, $selected:expr, $( [$func:expr, $part:expr] )*) => {{
+        use $crate::template::{differential::answer, runner::Part};
+        $( if $selected == $part { return answer(Part::solve(&$func, $input, $ctx)); } )*
+        Ok(None)
+    }};
+    (@answer [$parse:ident], $input:expr, $ctx:expr, $selected:expr, $( [$func:expr, $part:expr] )*) => {{
+        use $crate::template::{differential::answer, runner::{IntoParsed, Part}};
+        let parsed = Part::solve(&$parse, $input, $ctx).into_parsed()?;
+        $( if $selected == $part { return answer(Part::solve(&$func, &parsed, $ctx)); } )*
+        Ok(None)
+    }};
+
+    (@impl $day:expr, $variant:expr, [$($parse:ident)?], $( [$func:expr, $part:expr] )*) => {
         /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        pub const DAY: $crate::template::Day = $day;
+
+        /// The variant of the solution, `None` for the main one.
+        pub const VARIANT: Option<&str> = $variant;
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        /// Runs all parts of the solution against the puzzle input.
+        pub fn run() {
+            let input = $crate::template::read_input(DAY);
+            let ctx = $crate::template::Context::from_env();
+            $crate::solution!(@run [$($parse)?], input.as_str(), &ctx, $( [$func, $part] )*);
+        }
+
+        /// Solves a single part, with its answer converted to a string.
+        /// Used to compare the variants of a day, see [`differential`](advent_of_code::template::differential).
+        #[allow(dead_code)]
+        pub fn answer_part(
+            input: &str,
+            ctx: &$crate::template::Context,
+            part: u8,
+        ) -> Result<Option<String>, String> {
+            $crate::solution!(@answer [$($parse)?], input, ctx, part, $( [$func, $part] )*)
+        }
+
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            run();
         }
     };
 }
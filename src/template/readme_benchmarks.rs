@@ -1,11 +1,24 @@
-/// Module that updates the readme me with timing information.
-/// The approach taken is similar to how `aoc-readme-stars` handles this.
+//! Module that updates the readme me with timing information.
+//! The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::Day;
-use crate::template::timings::Timings;
+use crate::template::environment::Environment;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Day, Event, alloc, layout, report};
 
 static MARKER: &str = "<!--- benchmarking table --->";
+/// Delimits the note on the machine of the benchmarks, which is optional.
+static ENVIRONMENT_MARKER: &str = "<!--- benchmarking environment --->";
+
+/// Returns the marker that delimits an event's table.
+/// The current event uses the plain marker, others are qualified with their year.
+fn marker_for(event: Event) -> String {
+    if event == Event::current() {
+        MARKER.into()
+    } else {
+        format!("<!--- benchmarking table {event} --->")
+    }
+}
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -26,12 +39,12 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(day: Day, variant: Option<&str>) -> String {
+    layout::variant_bin_path(day, variant)
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,107 +65,185 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(prefix: &str, event: Event, timings: Timings, total_millis: f64) -> String {
+    let marker = marker_for(event);
+    let header = if event == Event::current() {
+        format!("{prefix} Benchmarks")
+    } else {
+        format!("{prefix} {event} Benchmarks")
+    };
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.clone(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        "| Day | Parse | Part 1 | Part 2 | Allocations |".into(),
+        "| :---: | :---: | :---: | :---: | :---: |".into(),
     ];
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let path = get_path_for_bin(timing.day, timing.variant.as_deref());
+        let label = match &timing.variant {
+            Some(variant) => format!("Day {} ({variant})", timing.day.into_inner()),
+            None => format!("Day {}", timing.day.into_inner()),
+        };
+        let cell = |part: u8, time: Option<String>| match time {
+            Some(time) => time,
+            None if timing.failed.contains(&part) => "failed".into(),
+            None if timing.timed_out.contains(&part) => "TIMEOUT".into(),
+            None if timing.out_of_memory.contains(&part) => "OOM".into(),
+            None => "-".into(),
+        };
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
+            "| [{}]({}) | `{}` | `{}` | `{}` | {} |",
+            label,
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            cell(report::PARSE, timing.parse.clone()),
+            cell(1, timing.part_1.clone()),
+            cell(2, timing.part_2.clone()),
+            allocations_cell(&timing)
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+/// Lists the allocations of each part that has them, flagging parts that do not allocate,
+/// e.g. `1: none ✔ · 2: 12 (peak 1.5 KiB)`.
+fn allocations_cell(timing: &Timing) -> String {
+    let parts: Vec<String> = [
+        ("parse", timing.parse_alloc),
+        ("1", timing.part_1_alloc),
+        ("2", timing.part_2_alloc),
+    ]
+    .into_iter()
+    .filter_map(|(part, alloc)| {
+        let alloc = alloc?;
+        Some(if alloc.allocations == 0 {
+            format!("{part}: none ✔")
+        } else {
+            format!(
+                "{part}: {} (peak {})",
+                alloc.allocations,
+                alloc::format_bytes(alloc.peak_bytes)
+            )
+        })
+    })
+    .collect();
+
+    if parts.is_empty() {
+        "-".into()
+    } else {
+        parts.join(" · ")
+    }
+}
+
+fn update_content(
+    s: &mut String,
+    event: Event,
+    timings: Timings,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let positions = locate_table(s, &marker_for(event))?;
+    let table = construct_table("##", event, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Replaces the note between the environment markers, if the readme has them.
+fn update_environment_content(s: &mut String, environment: &Environment) -> Result<(), Error> {
+    if !s.contains(ENVIRONMENT_MARKER) {
+        return Ok(());
+    }
+
+    let positions = locate_table(s, ENVIRONMENT_MARKER)?;
+    let note = format!(
+        "{ENVIRONMENT_MARKER}\n_{}_\n{ENVIRONMENT_MARKER}",
+        environment.note()
+    );
+    s.replace_range(positions.pos_start..positions.pos_end, &note);
+    Ok(())
+}
+
+/// Updates the benchmark table of an event in the readme.
+pub fn update(event: Event, timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, event, timings, total_millis)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+/// Updates the note on the machine of the benchmarks in the readme.
+pub fn update_environment(environment: &Environment) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_environment_content(&mut readme, environment)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
-#[cfg(feature = "test_lib")]
+#[cfg(all(test, feature = "test_lib"))]
 mod tests {
-    use super::{MARKER, update_content};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{ENVIRONMENT_MARKER, MARKER, update_content, update_environment_content};
+    use crate::{
+        day,
+        template::Event,
+        template::alloc::AllocStats,
+        template::environment::Environment,
+        template::timings::{
+            Timing, Timings,
+            mock::{mock_timing, mock_timings},
+        },
+    };
 
+    /// The shared mock timings, with a parse phase and allocations on day 4.
     fn get_mock_timings() -> Timings {
-        Timings {
-            data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
-                },
-            ],
-        }
+        let mut timings = mock_timings();
+        timings.data[2] = Timing {
+            parse: Some("1ms".into()),
+            part_1_alloc: Some(AllocStats::default()),
+            part_2_alloc: Some(AllocStats {
+                allocations: 12,
+                bytes: 4096,
+                peak_bytes: 1536,
+            }),
+            ..mock_timing(day!(4), Some("40ms"), Some("50ms"), 9e+10)
+        };
+        timings
     }
 
     #[test]
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Event::current(), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Event::current(), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        update_content(&mut s, Event::current(), get_mock_timings(), 190.0).unwrap();
+        assert!(s.contains("## Benchmarks"));
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Event::current(), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Event::current(), get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,18 +251,18 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Event::current(), get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 | Allocations |",
+            "| :---: | :---: | :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` | - |",
+            "| [Day 2](./src/bin/02.rs) | `-` | `30ms` | `40ms` | - |",
+            "| [Day 4](./src/bin/04.rs) | `1ms` | `40ms` | `50ms` | 1: none ✔ · 2: 12 (peak 1.5 KiB) |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +271,58 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn updates_environment_note() {
+        let mut s = format!("foo\n{ENVIRONMENT_MARKER}\n_hand-written_\n{ENVIRONMENT_MARKER}");
+        let environment = Environment {
+            cpu_model: Some("Apple M2".into()),
+            os: "macos".into(),
+            ..Environment::default()
+        };
+
+        update_environment_content(&mut s, &environment).unwrap();
+        assert_eq!(
+            s,
+            format!(
+                "foo\n{ENVIRONMENT_MARKER}\n_Rust benchmarked on Apple M2 using macos_\n{ENVIRONMENT_MARKER}"
+            )
+        );
+    }
+
+    #[test]
+    fn keeps_tables_of_events_apart() {
+        let event = Event::new(2015).unwrap();
+        let marker_2015 = "<!--- benchmarking table 2015 --->";
+        let mut s = format!("{MARKER}{MARKER}\n{marker_2015}{marker_2015}");
+
+        let timings = Timings {
+            data: vec![mock_timing(day!(25, year = 2015), Some("1ms"), None, 1e+6)],
+            history: vec![],
+            environment: None,
+        };
+
+        update_content(&mut s, event, timings, 1.0).unwrap();
+        assert!(s.starts_with(&format!(
+            "{MARKER}{MARKER}\n{marker_2015}\n## 2015 Benchmarks"
+        )));
+        assert!(s.contains("| [Day 25](./src/bin/2015_25.rs) | `-` | `1ms` | `-` | - |"));
+    }
+
+    #[test]
+    fn labels_variants() {
+        let mut s = format!("{MARKER}{MARKER}");
+
+        let timings = Timings {
+            data: vec![Timing {
+                variant: Some("fast".into()),
+                ..mock_timing(day!(8), Some("1ms"), Some("2ms"), 3e+6)
+            }],
+            history: vec![],
+            environment: None,
+        };
+
+        update_content(&mut s, Event::current(), timings, 3.0).unwrap();
+        assert!(s.contains("| [Day 8 (fast)](./src/bin/08_fast.rs) | `-` | `1ms` | `2ms` | - |"));
+    }
 }
@@ -0,0 +1,109 @@
+//! In-process execution of several solutions from a single binary.
+//!
+//! The `all` binary links every solution in `src/bin/` (the list is generated by
+//! `build.rs`) and runs the requested days one after another, sparing a `cargo run`
+//! invocation per day. Solutions emit their reports through [`runner::run_part`] as usual.
+//!
+//! [`runner::run_part`]: crate::template::runner::run_part
+use std::{env, panic, process, str::FromStr};
+
+use crate::template::{Day, Event, run_multi::print_header};
+
+/// Argument that lists the days to run, e.g. `--days 2025:01,2025:02`.
+pub const DAYS_ARG: &str = "--days";
+
+/// The name of the binary that links all solutions.
+pub const BIN_NAME: &str = "all";
+
+/// A solution linked into the `all` binary.
+pub struct Solution {
+    pub day: Day,
+    pub variant: Option<&'static str>,
+    /// Runs all parts of the solution against the puzzle input.
+    pub run: fn(),
+}
+
+/// Serializes days into the value of [`DAYS_ARG`].
+pub fn days_to_arg(days: &[Day]) -> String {
+    days.iter()
+        .map(|day| format!("{}:{day}", day.event()))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Parses the value of [`DAYS_ARG`].
+pub fn days_from_arg(arg: &str) -> Option<Vec<Day>> {
+    arg.split(',')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let (year, day) = s.split_once(':')?;
+            Day::parse_in(Event::from_str(year).ok()?, day).ok()
+        })
+        .collect()
+}
+
+/// Runs all solutions of the days passed in [`DAYS_ARG`], in order.
+/// A panicking solution is reported and does not prevent later days from running.
+pub fn run(solutions: &[Solution]) {
+    let args: Vec<String> = env::args().collect();
+
+    let Some(days) = args
+        .iter()
+        .position(|x| x == DAYS_ARG)
+        .and_then(|i| args.get(i + 1))
+        .and_then(|arg| days_from_arg(arg))
+    else {
+        eprintln!("Unexpected command-line input. Format: all --days 2025:01,2025:02");
+        process::exit(1);
+    };
+
+    // NOTE: `build.rs` links no solutions when profiling with dhat. Exiting unsuccessfully
+    // without reports makes `run_multi` re-run every day in its own binary.
+    if solutions.is_empty() {
+        eprintln!("No solutions are linked in-process, running them in their own binaries.");
+        process::exit(1);
+    }
+
+    let mut need_space = false;
+
+    for day in days {
+        let mut day_solutions = solutions.iter().filter(|s| s.day == day).peekable();
+
+        if day_solutions.peek().is_none() {
+            print_header(day, None, &mut need_space);
+            println!("Not solved.");
+        }
+
+        for solution in day_solutions {
+            print_header(day, solution.variant, &mut need_space);
+
+            if panic::catch_unwind(solution.run).is_err() {
+                eprintln!("Solution for day {day} panicked.");
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{days_from_arg, days_to_arg};
+    use crate::day;
+
+    #[test]
+    fn round_trips_days() {
+        let days = vec![day!(1), day!(12), day!(25, year = 2015)];
+        let arg = days_to_arg(&days);
+
+        assert_eq!(arg, "2025:01,2025:12,2015:25");
+        assert_eq!(days_from_arg(&arg), Some(days));
+    }
+
+    #[test]
+    fn rejects_invalid_days() {
+        assert_eq!(days_from_arg("2025:13"), None);
+        assert_eq!(days_from_arg("01"), None);
+        assert_eq!(days_from_arg(""), Some(vec![]));
+    }
+}
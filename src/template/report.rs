@@ -0,0 +1,417 @@
+//! Machine-readable records exchanged between solution binaries and the runner.
+//!
+//! When the `AOC_REPORT_FILE` environment variable is set, every call to
+//! `runner::run_part` appends one JSON object per line to that file. The runner
+//! reads these records back instead of scraping the human-formatted stdout.
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    Day,
+    alloc::AllocStats,
+    counters::Counters,
+    day::{day_from_json, day_to_json},
+    stats::Stats,
+};
+
+pub const REPORT_FILE_ENV: &str = "AOC_REPORT_FILE";
+
+/// The part number that the parse phase of two-phase solutions is reported and timed as,
+/// see [`runner::run_parse`](crate::template::runner::run_parse).
+pub const PARSE: u8 = 0;
+
+/// Names a part in output, e.g. `Part 1`, or `Parse` for [`PARSE`].
+pub fn part_label(part: u8) -> String {
+    if part == PARSE {
+        "Parse".into()
+    } else {
+        format!("Part {part}")
+    }
+}
+
+/// The outcome of running a single solution part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    /// The part returned no answer, i.e. it is not implemented.
+    Unsolved,
+    /// The part returned an error.
+    Failed,
+    /// The part exceeded its time limit, see [`limits`](crate::template::limits).
+    Timeout,
+    /// The part exceeded its memory limit.
+    OutOfMemory,
+}
+
+impl Status {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Failed => "failed",
+            Status::Timeout => "timeout",
+            Status::OutOfMemory => "oom",
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(Status::Solved),
+            "unsolved" => Ok(Status::Unsolved),
+            "failed" => Ok(Status::Failed),
+            "timeout" => Ok(Status::Timeout),
+            "oom" => Ok(Status::OutOfMemory),
+            _ => Err(format!("unknown status `{s}`.")),
+        }
+    }
+}
+
+/// A single record describing the result of one solution part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub day: Day,
+    /// The variant of the solution, `None` for the main one.
+    pub variant: Option<String>,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub nanos: f64,
+    pub samples: u64,
+    pub status: Status,
+    /// The error message of a failed part.
+    pub error: Option<String>,
+    /// Sample statistics, present when the part was benched.
+    pub stats: Option<Stats>,
+    /// The heap usage of the first run, unless it could not be counted.
+    pub alloc: Option<AllocStats>,
+    /// Hardware counters per iteration, present when the part was benched with `--counters`.
+    pub counters: Option<Counters>,
+}
+
+impl PartReport {
+    /// Reports a part as failed with `error` when it could not report itself, e.g. because
+    /// its binary could not be run.
+    pub fn failed(day: Day, variant: Option<&str>, part: u8, error: String) -> Self {
+        PartReport {
+            day,
+            variant: variant.map(Into::into),
+            part,
+            answer: None,
+            nanos: 0_f64,
+            samples: 0,
+            status: Status::Failed,
+            error: Some(error),
+            stats: None,
+            alloc: None,
+            counters: None,
+        }
+    }
+}
+
+/// Appends the report to the file named by `AOC_REPORT_FILE`, if set.
+pub fn emit(report: &PartReport) -> io::Result<()> {
+    let Some(path) = env::var_os(REPORT_FILE_ENV) else {
+        return Ok(());
+    };
+
+    let line = JsonValue::from(report)
+        .stringify()
+        .map_err(|e| io::Error::other(e.to_string()))?;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Reads all reports from a JSON lines file. A missing file yields no reports.
+pub fn read_file(path: &Path) -> Result<Vec<PartReport>, String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => parse_lines(&contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Parses JSON lines into reports, skipping blank lines.
+pub fn parse_lines(contents: &str) -> Result<Vec<PartReport>, String> {
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let json = JsonValue::from_str(line).or(Err("report is not valid JSON."))?;
+            PartReport::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        day_to_json(&mut map, value.day);
+        map.insert(
+            "variant".into(),
+            match &value.variant {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        if let Some(error) = &value.error {
+            map.insert("error".into(), JsonValue::String(error.clone()));
+        }
+
+        map.insert(
+            "stats".into(),
+            match &value.stats {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        if let Some(alloc) = &value.alloc {
+            map.insert("alloc".into(), JsonValue::from(alloc));
+        }
+
+        if let Some(counters) = &value.counters {
+            map.insert("counters".into(), JsonValue::from(counters));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartReport {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let day = day_from_json(json).ok_or("Expected report.day to be a Day struct.")?;
+
+        let variant = match json.get("variant") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected report.variant to be null or string.")?
+                    .clone(),
+            ),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|&p| p as u8)
+            .filter(|p| (PARSE..=2).contains(p))
+            .ok_or("Expected report.part to be 0, 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected report.nanos to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|&s| s as u64)
+            .ok_or("Expected report.samples to be a number.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected report.status to be a string.")?
+            .parse()?;
+
+        let error = match json.get("error") {
+            None => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected report.error to be a string.")?
+                    .clone(),
+            ),
+        };
+
+        let stats = match json.get("stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(Stats::try_from(v)?),
+        };
+
+        let alloc = match json.get("alloc") {
+            None => None,
+            Some(v) => Some(AllocStats::try_from(v)?),
+        };
+
+        let counters = match json.get("counters") {
+            None => None,
+            Some(v) => Some(Counters::try_from(v)?),
+        };
+
+        Ok(PartReport {
+            day,
+            variant,
+            part,
+            answer: answer.cloned(),
+            nanos,
+            samples,
+            status,
+            error,
+            stats,
+            alloc,
+            counters,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{PartReport, Status, parse_lines};
+    use crate::{
+        day,
+        template::{alloc::AllocStats, counters::Counters, stats::Stats},
+    };
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn round_trips_reports() {
+        let report = PartReport {
+            day: day!(3),
+            variant: Some("fast".into()),
+            part: 2,
+            answer: Some("42 (@ 1 samples)".into()),
+            nanos: 74.13,
+            samples: 5,
+            status: Status::Solved,
+            error: None,
+            stats: Some(Stats {
+                samples: 5,
+                min: 70.0,
+                median: 74.13,
+                mean: 75.0,
+                p95: 80.0,
+                std_dev: 1.5,
+                outliers: 1,
+            }),
+            alloc: Some(AllocStats {
+                allocations: 3,
+                bytes: 1536,
+                peak_bytes: 1024,
+            }),
+            counters: Some(Counters {
+                instructions: Some(1200.0),
+                cycles: Some(400.0),
+                ..Counters::default()
+            }),
+        };
+
+        let line = JsonValue::from(&report).stringify().unwrap();
+        let parsed = parse_lines(&line).unwrap();
+        assert_eq!(parsed, vec![report]);
+    }
+
+    #[test]
+    fn round_trips_failures() {
+        let report = PartReport {
+            day: day!(2),
+            variant: None,
+            part: 1,
+            answer: None,
+            nanos: 10.0,
+            samples: 1,
+            status: Status::Failed,
+            error: Some("line 1, column 3: expected a number".into()),
+            stats: None,
+            alloc: None,
+            counters: None,
+        };
+
+        let line = JsonValue::from(&report).stringify().unwrap();
+        assert_eq!(parse_lines(&line).unwrap(), vec![report]);
+    }
+
+    #[test]
+    fn round_trips_limit_breaches() {
+        for status in [Status::Timeout, Status::OutOfMemory] {
+            let report = PartReport {
+                day: day!(10),
+                variant: None,
+                part: 2,
+                answer: None,
+                nanos: 1e9,
+                samples: 1,
+                status,
+                error: Some("timed out after 1.0s".into()),
+                stats: None,
+                alloc: None,
+                counters: None,
+            };
+
+            let line = JsonValue::from(&report).stringify().unwrap();
+            assert_eq!(parse_lines(&line).unwrap(), vec![report]);
+        }
+    }
+
+    #[test]
+    fn parses_multiple_lines() {
+        let contents = [
+            r#"{"day":"01","part":1,"answer":"3","nanos":10,"samples":1,"status":"solved"}"#,
+            "",
+            r#"{"day":"01","part":2,"answer":null,"nanos":0,"samples":1,"status":"unsolved"}"#,
+        ]
+        .join("\n");
+
+        let reports = parse_lines(&contents).unwrap();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].answer, Some("3".into()));
+        assert_eq!(reports[1].status, Status::Unsolved);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_part() {
+        parse_lines(
+            r#"{"day":"01","part":3,"answer":null,"nanos":0,"samples":1,"status":"solved"}"#,
+        )
+        .unwrap();
+    }
+}
@@ -1,49 +1,191 @@
-use std::{collections::HashSet, io};
-
-use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use std::{
+    collections::HashSet,
+    fmt::{self, Display},
+    io,
+    num::NonZeroUsize,
+    path::Path,
+};
 
-use super::{
-    all_days,
-    timings::{Timing, Timings},
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, environment::Environment, layout, limits::Limits,
+    report::PartReport, runner::BenchConfig,
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+use super::timings::Timings;
 
-    let mut need_space = false;
+/// The outcome of running the solutions of several days.
+pub struct MultiRun {
+    /// Reports of every part that was run, in day order.
+    pub reports: Vec<PartReport>,
+    /// Benchmark timings, present for timed runs only.
+    pub timings: Option<Timings>,
+}
+
+/// How [`run_multi`] executes the solutions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// All days run in-process from the `all` binary (see [`registry`]).
+    /// If that process does not exit cleanly, e.g. because a solution aborted it, the days
+    /// that did not report back are re-run in their own binary.
+    ///
+    /// [`registry`]: crate::template::registry
+    #[default]
+    InProcess,
+    /// Every day runs in its own binary, one after another.
+    Isolated,
+    /// Every day runs in its own binary, with up to `n` binaries running concurrently.
+    /// Output is buffered and printed in day order.
+    Parallel(NonZeroUsize),
+}
+
+/// Runs the solutions of the given days, including all their variants.
+/// Solutions are benched when a `bench` configuration is given. `limits` override the limits
+/// of all days configured in `data/limits.json`, see [`limits`](crate::template::limits).
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    bench: Option<&BenchConfig>,
+    mode: Mode,
+    limits: &Limits,
+) -> MultiRun {
+    let mut all_reports: Vec<PartReport> = vec![];
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+    let mut days: Vec<Day> = days_to_run.iter().copied().collect();
+    days.sort_unstable();
+
+    // days that have not been scaffolded yet are kept as their main solution.
+    let solutions: Vec<(Day, Option<String>)> = days
+        .iter()
+        .flat_map(|&day| {
+            let variants = layout::variants(day);
+            if variants.is_empty() {
+                vec![(day, None)]
+            } else {
+                variants.into_iter().map(|v| (day, v)).collect()
             }
-            need_space = true;
+        })
+        .collect();
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    let mut pending = solutions.clone();
+    let mut need_space = false;
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let mode = match mode {
+        Mode::Parallel(_) if bench.is_some() => {
+            eprintln!("Parallel runs distort timings, running days one after another.");
+            Mode::Isolated
+        }
+        // NOTE: the registry is empty when profiling with dhat, see `build.rs`.
+        Mode::InProcess if cfg!(feature = "dhat-heap") => Mode::Isolated,
+        mode => mode,
+    };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+    if let Mode::Parallel(jobs) = mode {
+        pending.clear();
+
+        child_commands::run_solutions_parallel(
+            &solutions,
+            jobs,
+            is_release,
+            limits,
+            |day, variant, result| {
+                print_header(day, variant, &mut need_space);
+
+                match result {
+                    Ok(Some(run)) => {
+                        print!("{}", run.stdout);
+                        eprint!("{}", run.stderr);
+                        all_reports.extend(run.reports);
+                    }
+                    Ok(None) => println!("Not solved."),
+                    Err(e) => all_reports.extend(failed_run(day, variant, &e)),
+                }
+            },
+        );
+    }
+
+    if mode == Mode::InProcess {
+        match child_commands::run_registry(&days, bench, is_release, limits) {
+            Ok((reports, success)) => {
+                pending.retain(|(day, variant)| {
+                    !success
+                        && Path::new(&get_path_for_bin(*day, variant.as_deref())).exists()
+                        && !reports
+                            .iter()
+                            .any(|r| r.day == *day && r.variant == *variant)
+                });
+                all_reports.extend(reports);
+                need_space = true;
             }
-        });
+            Err(e) => eprintln!("Failed to run solutions in-process: {e:?}"),
+        }
+    }
+
+    pending.into_iter().for_each(|(day, variant)| {
+        let variant = variant.as_deref();
+        print_header(day, variant, &mut need_space);
+
+        match child_commands::run_solution(day, variant, None, bench, is_release, limits) {
+            Ok(Some(reports)) => all_reports.extend(reports),
+            Ok(None) => println!("Not solved."),
+            Err(e) => all_reports.extend(failed_run(day, variant, &e)),
+        }
+    });
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    all_reports.sort_by(|a, b| (a.day, &a.variant, a.part).cmp(&(b.day, &b.variant, b.part)));
+
+    let timings = bench.map(|bench| {
+        let data = solutions
+            .iter()
+            .filter(|(day, variant)| {
+                Path::new(&get_path_for_bin(*day, variant.as_deref())).exists()
+            })
+            .map(|(day, variant)| {
+                child_commands::collect_timing(&all_reports, *day, variant.as_deref())
+            })
+            .collect();
+
+        let timings = Timings {
+            data,
+            history: vec![],
+            environment: Some(Environment::detect(bench.pin)),
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
+        timings
+    });
+
+    MultiRun {
+        reports: all_reports,
+        timings,
+    }
+}
+
+/// Names a solution in output, e.g. `Day 08` or `Day 08 (fast)` for a variant.
+pub fn label(day: Day, variant: Option<&str>) -> String {
+    match variant {
+        Some(variant) => format!("Day {day} ({variant})"),
+        None => format!("Day {day}"),
+    }
+}
+
+/// Reports both parts of a solution whose binary could not be run as failed, so that the
+/// other days still run and summaries count the day as failed.
+fn failed_run(day: Day, variant: Option<&str>, e: &Error) -> [PartReport; 2] {
+    eprintln!("Failed to run day {day}: {e}");
+    [1, 2].map(|part| PartReport::failed(day, variant, part, e.to_string()))
+}
+
+pub(crate) fn print_header(day: Day, variant: Option<&str>, need_space: &mut bool) {
+    if *need_space {
+        println!();
     }
+    *need_space = true;
+
+    println!("{ANSI_BOLD}{}{ANSI_RESET}", label(day, variant));
+    println!("------");
 }
 
 #[allow(dead_code)]
@@ -51,6 +193,7 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
+    Report(String),
 }
 
 impl From<std::io::Error> for Error {
@@ -59,57 +202,226 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "the output of the solution could not be read."),
+            Error::IO(e) => write!(f, "{e}"),
+            Error::Report(e) => write!(f, "malformed report: {e}"),
+        }
+    }
+}
+
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(day: Day, variant: Option<&str>) -> String {
+    layout::variant_bin_path(day, variant)
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their reports.
 pub mod child_commands {
     use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use crate::template::timings::Timing;
+    use crate::template::{
+        Day, INPUT_FILE_ENV, layout,
+        limits::Limits,
+        registry::{self, DAYS_ARG},
+        report::{self, PartReport, REPORT_FILE_ENV, Status},
+        runner::BenchConfig,
+    };
     use std::{
+        collections::BTreeMap,
+        env, fs,
         io::{BufRead, BufReader},
-        path::Path,
+        num::NonZeroUsize,
+        path::{Path, PathBuf},
         process::{Command, Stdio},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            mpsc,
+        },
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day and variant, returning the reports emitted by its parts.
+    /// The solution reads `input` instead of its puzzle input, if given.
+    /// Returns `None` if the solution has not been scaffolded yet.
+    pub fn run_solution(
+        day: Day,
+        variant: Option<&str>,
+        input: Option<&Path>,
+        bench: Option<&BenchConfig>,
+        is_release: bool,
+        limits: &Limits,
+    ) -> Result<Option<Vec<PartReport>>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&get_path_for_bin(day, variant)).exists() {
+            return Ok(None);
+        }
+
+        let bin = layout::variant_bin_name(day, variant);
+        let args = get_args(&bin, &[], bench, is_release);
+        let (reports, _) = run_with_reports(&args, &get_report_path(&bin), input, limits)?;
+        Ok(Some(reports))
+    }
+
+    /// The buffered output and reports of a solution bin.
+    pub struct BufferedRun {
+        pub reports: Vec<PartReport>,
+        pub stdout: String,
+        pub stderr: String,
+    }
+
+    /// Run the solution bin for a given day and variant, buffering its output.
+    /// Returns `None` if the solution has not been scaffolded yet.
+    pub fn run_solution_buffered(
+        day: Day,
+        variant: Option<&str>,
+        is_release: bool,
+        limits: &Limits,
+    ) -> Result<Option<BufferedRun>, Error> {
+        if !Path::new(&get_path_for_bin(day, variant)).exists() {
+            return Ok(None);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin = layout::variant_bin_name(day, variant);
+        let args = get_args(&bin, &[], None, is_release);
+        let report_path = get_report_path(&bin);
+        let _ = fs::remove_file(&report_path);
+
+        let mut cmd = Command::new("cargo");
+        cmd.args(&args).env(REPORT_FILE_ENV, &report_path);
+        limits.apply_to(&mut cmd);
+        let output = cmd.output()?;
+
+        let reports = report::read_file(&report_path).map_err(Error::Report)?;
+        let _ = fs::remove_file(&report_path);
+
+        Ok(Some(BufferedRun {
+            reports,
+            stdout: String::from_utf8_lossy(&output.stdout).into(),
+            stderr: String::from_utf8_lossy(&output.stderr).into(),
+        }))
+    }
+
+    /// Run several solution bins with up to `jobs` bins running concurrently.
+    /// Results are passed to `on_result` in the given order, as soon as all preceding bins are done.
+    pub fn run_solutions_parallel(
+        solutions: &[(Day, Option<String>)],
+        jobs: NonZeroUsize,
+        is_release: bool,
+        limits: &Limits,
+        mut on_result: impl FnMut(Day, Option<&str>, Result<Option<BufferedRun>, Error>),
+    ) {
+        let next = AtomicUsize::new(0);
+        let (tx, rx) = mpsc::channel();
+
+        thread::scope(|scope| {
+            for _ in 0..jobs.get().min(solutions.len()) {
+                let tx = tx.clone();
+                let next = &next;
+
+                scope.spawn(move || {
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some((day, variant)) = solutions.get(i) else {
+                            break;
+                        };
+
+                        let result =
+                            run_solution_buffered(*day, variant.as_deref(), is_release, limits);
+                        if tx.send((i, result)).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+
+            drop(tx);
+
+            // NOTE: solutions are handed out in order, so results can be released in that order too.
+            let mut done = BTreeMap::new();
+            let mut released = 0;
+
+            for (i, result) in rx {
+                done.insert(i, result);
+
+                while let Some(result) = done.remove(&released) {
+                    let (day, variant) = &solutions[released];
+                    on_result(*day, variant.as_deref(), result);
+                    released += 1;
+                }
+            }
+        });
+    }
+
+    /// Run the `all` binary for the given days, returning the reports emitted by their parts
+    /// and whether the process exited successfully.
+    pub fn run_registry(
+        days: &[Day],
+        bench: Option<&BenchConfig>,
+        is_release: bool,
+        limits: &Limits,
+    ) -> Result<(Vec<PartReport>, bool), Error> {
+        let days_arg = [DAYS_ARG.to_string(), registry::days_to_arg(days)];
+        let args = get_args(registry::BIN_NAME, &days_arg, bench, is_release);
+        run_with_reports(&args, &get_report_path(registry::BIN_NAME), None, limits)
+    }
+
+    fn get_args(
+        bin: &str,
+        bin_args: &[String],
+        bench: Option<&BenchConfig>,
+        is_release: bool,
+    ) -> Vec<String> {
+        let mut args: Vec<String> = vec!["run".into(), "--quiet".into(), "--bin".into()];
+        args.push(bin.into());
 
         if is_release {
-            args.push("--release");
+            args.push("--release".into());
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        if bench.is_some_and(|bench| bench.alloc) {
+            args.extend(["--features".into(), "alloc-stats".into()]);
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        args.push("--".into());
+        args.extend_from_slice(bin_args);
+
+        if let Some(bench) = bench {
+            // mirror `--time` flag and bench parameters to child invocations.
+            args.push("--time".into());
+            args.extend(bench.to_args());
+        }
+
+        args
+    }
+
+    fn run_with_reports(
+        args: &[String],
+        report_path: &Path,
+        input: Option<&Path>,
+        limits: &Limits,
+    ) -> Result<(Vec<PartReport>, bool), Error> {
+        let _ = fs::remove_file(report_path);
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        // spawn child command with piped stdout/stderr and a report side channel.
+        // forward output to stdout/stderr as it arrives.
+
+        let mut cmd = Command::new("cargo");
+        cmd.args(args).env(REPORT_FILE_ENV, report_path);
+
+        if let Some(input) = input {
+            cmd.env(INPUT_FILE_ENV, input);
+        }
+
+        limits.apply_to(&mut cmd);
+
+        let mut cmd = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
         let thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
                 eprintln!("{}", line.unwrap());
@@ -117,80 +429,94 @@ pub mod child_commands {
         });
 
         for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            println!("{}", line?);
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
+
+        let reports = report::read_file(report_path).map_err(Error::Report)?;
+        let _ = fs::remove_file(report_path);
 
-        Ok(output)
+        Ok((reports, status.success()))
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+    fn get_report_path(bin: &str) -> PathBuf {
+        env::temp_dir().join(format!("aoc-report-{bin}-{}.jsonl", std::process::id()))
+    }
+
+    /// Builds the timing for a day from the reports of its solved parts.
+    pub fn collect_timing(reports: &[PartReport], day: Day, variant: Option<&str>) -> Timing {
+        let mut timings = Timing {
+            variant: variant.map(Into::into),
+            ..Timing::new(day)
         };
 
-        output
+        let reports = reports
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+            .filter(|r| r.day == day && r.variant.as_deref() == variant);
+
+        for r in reports.clone() {
+            match r.status {
+                Status::Failed => timings.failed.push(r.part),
+                Status::Timeout => timings.timed_out.push(r.part),
+                Status::OutOfMemory => timings.out_of_memory.push(r.part),
+                Status::Solved | Status::Unsolved => {}
+            }
+        }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
+        reports
+            .filter(|r| r.status == Status::Solved)
+            .for_each(|r| {
+                let timing_str = Some(format_nanos(r.nanos));
+
+                let (time, stats, alloc, counters) = match r.part {
+                    report::PARSE => (
+                        &mut timings.parse,
+                        &mut timings.parse_stats,
+                        &mut timings.parse_alloc,
+                        &mut timings.parse_counters,
+                    ),
+                    1 => (
+                        &mut timings.part_1,
+                        &mut timings.part_1_stats,
+                        &mut timings.part_1_alloc,
+                        &mut timings.part_1_counters,
+                    ),
+                    2 => (
+                        &mut timings.part_2,
+                        &mut timings.part_2_stats,
+                        &mut timings.part_2_alloc,
+                        &mut timings.part_2_counters,
+                    ),
+                    _ => return,
                 };
+                (*time, *stats, *alloc, *counters) =
+                    (timing_str, r.stats.clone(), r.alloc, r.counters);
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
-
-                timings.total_nanos += nanos;
+                timings.total_nanos += r.nanos;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+    /// Formats a mean duration for `timings.json`. Unlike `Duration`, this keeps the fractions
+    /// of a nanosecond that averaging over samples produces, e.g. `74.13ns`.
+    fn format_nanos(nanos: f64) -> String {
+        let (value, unit) = match nanos {
+            n if n >= 1e9 => (n / 1e9, "s"),
+            n if n >= 1e6 => (n / 1e6, "ms"),
+            n if n >= 1e3 => (n / 1e3, "µs"),
+            n => (n, "ns"),
+        };
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .next_back()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        let value = format!("{value:.2}");
+        let value = value.trim_end_matches('0').trim_end_matches('.');
+        format!("{value}{unit}")
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
+    #[cfg(all(test, feature = "test_lib"))]
     macro_rules! assert_approx_eq {
         ($a:expr, $b:expr) => {{
             let (a, b) = (&$a, &$b);
@@ -203,55 +529,144 @@ pub mod child_commands {
         }};
     }
 
-    #[cfg(feature = "test_lib")]
+    #[cfg(all(test, feature = "test_lib"))]
     mod tests {
-        use super::parse_exec_time;
+        use super::collect_timing;
+
+        use crate::{
+            day,
+            template::report::{PartReport, Status},
+        };
 
-        use crate::day;
+        fn report(part: u8, answer: Option<&str>, nanos: f64) -> PartReport {
+            PartReport {
+                day: day!(1),
+                variant: None,
+                part,
+                answer: answer.map(Into::into),
+                nanos,
+                samples: 100,
+                stats: None,
+                alloc: None,
+                counters: None,
+                status: if answer.is_some() {
+                    Status::Solved
+                } else {
+                    Status::Unsolved
+                },
+                error: None,
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn collects_execution_times() {
+            let res = collect_timing(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    report(1, Some("0"), 74.13),
+                    report(2, Some("10"), 74_130_000.0),
                 ],
                 day!(1),
+                None,
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
+            assert_approx_eq!(res.total_nanos, 74_130_074.13_f64);
             assert_eq!(res.part_1.unwrap(), "74.13ns");
             assert_eq!(res.part_2.unwrap(), "74.13ms");
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn collects_with_patterns_in_answers() {
+            let res = collect_timing(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    report(1, Some("@ @ @ ( ) ms (2s @ 5 samples)"), 2e9),
+                    report(2, Some("10s"), 1e8),
                 ],
                 day!(1),
+                None,
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
+            assert_eq!(res.total_nanos, 2_100_000_000_f64);
             assert_eq!(res.part_1.unwrap(), "2s");
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
+        fn collects_missing_parts() {
+            let res = collect_timing(&[report(1, None, 5.0), report(2, None, 5.0)], day!(1), None);
+            assert_eq!(res.total_nanos, 0_f64);
+            assert!(res.part_1.is_none());
+            assert!(res.part_2.is_none());
+            assert!(res.failed.is_empty());
+        }
+
+        #[test]
+        fn collects_failed_parts() {
+            let mut failed = report(2, None, 5.0);
+            failed.status = Status::Failed;
+
+            let res = collect_timing(&[report(1, Some("0"), 5.0), failed], day!(1), None);
+            assert_eq!(res.total_nanos, 5_f64);
+            assert_eq!(res.part_2, None);
+            assert_eq!(res.failed, vec![2]);
+        }
+
+        #[test]
+        fn collects_parts_over_limits() {
+            let mut timed_out = report(1, None, 5.0);
+            timed_out.status = Status::Timeout;
+            let mut out_of_memory = report(2, None, 5.0);
+            out_of_memory.status = Status::OutOfMemory;
+
+            let res = collect_timing(&[timed_out, out_of_memory], day!(1), None);
+            assert_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.timed_out, vec![1]);
+            assert_eq!(res.out_of_memory, vec![2]);
+            assert!(res.failed.is_empty());
+        }
+
+        #[test]
+        fn collects_runs_that_could_not_start() {
+            let failed = [1, 2].map(|part| {
+                PartReport::failed(day!(1), None, part, "could not spawn cargo".into())
+            });
+
+            let res = collect_timing(&failed, day!(1), None);
+            assert_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.failed, vec![1, 2]);
+        }
+
+        #[test]
+        fn collects_parse_times() {
+            let parse = report(0, None, 1_000.0);
+            let parse = PartReport {
+                status: Status::Solved,
+                ..parse
+            };
+
+            let res = collect_timing(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    parse,
+                    report(1, Some("1"), 10.0),
+                    report(2, Some("2"), 20.0),
                 ],
                 day!(1),
+                None,
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.total_nanos, 1_030_f64);
+            assert_eq!(res.parse.unwrap(), "1µs");
+            assert_eq!(res.part_1.unwrap(), "10ns");
+        }
+
+        #[test]
+        fn collects_variants_separately() {
+            let mut fast = report(1, Some("0"), 10.0);
+            fast.variant = Some("fast".into());
+            let reports = [report(1, Some("0"), 74.0), fast];
+
+            let main = collect_timing(&reports, day!(1), None);
+            assert_eq!(main.total_nanos, 74_f64);
+
+            let variant = collect_timing(&reports, day!(1), Some("fast"));
+            assert_eq!(variant.variant.as_deref(), Some("fast"));
+            assert_eq!(variant.total_nanos, 10_f64);
         }
     }
 }
@@ -1,35 +1,401 @@
-/// Encapsulates code that interacts with solution functions.
+//! Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{Write, stdout};
-use std::process::Output;
+use std::sync::Once;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::alloc::{self, AllocStats};
+use crate::template::answers::Answers;
+use crate::template::counters::{CounterGroup, Counters};
+use crate::template::environment;
+use crate::template::limits;
+use crate::template::report::{self, PartReport, Status};
+use crate::template::stats::Stats;
+use crate::template::submissions::Submissions;
+use crate::template::{ANSI_ITALIC, ANSI_RESET, Context, Day, InputKind, aoc_client, clock};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Default duration of the measurement phase when benching.
+const DEFAULT_BENCH_TIME: Duration = Duration::from_secs(1);
+/// Minimum number of samples collected, regardless of the target duration.
+const MIN_SAMPLES: usize = 10;
+/// Upper bound on collected samples, to keep memory usage of very fast solutions in check.
+const MAX_SAMPLES: usize = 100_000;
+/// Number of times a benchmark is repeated at most when its samples vary too much.
+const MAX_RERUNS: usize = 3;
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+/// Parameters of the benchmark, forwarded from `cargo time` to the solution binaries.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Approximate duration of the measurement phase.
+    pub target_time: Duration,
+    /// Duration of the warm-up phase preceding the measurements.
+    pub warm_up: Duration,
+    /// Whether hardware counters are read in an extra pass after the measurements.
+    pub counters: bool,
+    /// The CPU that the solution binaries are pinned to while benching.
+    pub pin: Option<usize>,
+    /// The highest standard deviation of the samples that is accepted, in percent of their
+    /// mean. Benchmarks that vary more are re-run up to [`MAX_RERUNS`] times.
+    pub max_rsd: Option<u32>,
+    /// Whether the solutions are built with the `alloc-stats` feature, which counts the
+    /// allocations of every part, see [`alloc`].
+    pub alloc: bool,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self::with_target_time(DEFAULT_BENCH_TIME)
+    }
+}
+
+impl BenchConfig {
+    /// Creates a configuration for the given target time, warming up for a tenth of it.
+    pub fn with_target_time(target_time: Duration) -> Self {
+        Self {
+            target_time,
+            warm_up: target_time / 10,
+            counters: false,
+            pin: None,
+            max_rsd: None,
+            alloc: false,
+        }
+    }
+
+    /// Reads the configuration from the command-line arguments of a solution binary.
+    pub fn from_args(args: &[String]) -> Self {
+        let value = |name: &str| {
+            args.iter()
+                .position(|x| x == name)
+                .and_then(|i| args.get(i + 1))
+        };
+        let millis = |name: &str| {
+            value(name)
+                .and_then(|x| x.parse().ok())
+                .map(Duration::from_millis)
+        };
+
+        let config = millis("--bench-time").map_or_else(Self::default, Self::with_target_time);
+
+        Self {
+            warm_up: millis("--warm-up").unwrap_or(config.warm_up),
+            counters: args.iter().any(|x| x == "--counters"),
+            pin: value("--pin").and_then(|x| x.parse().ok()),
+            max_rsd: value("--max-rsd").and_then(|x| x.parse().ok()),
+            alloc: alloc::COUNTING,
+            ..config
+        }
+    }
+
+    /// Serializes the configuration into arguments understood by [`BenchConfig::from_args`].
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--bench-time".into(),
+            self.target_time.as_millis().to_string(),
+            "--warm-up".into(),
+            self.warm_up.as_millis().to_string(),
+        ];
+
+        if self.counters {
+            args.push("--counters".into());
+        }
+
+        if let Some(cpu) = self.pin {
+            args.extend(["--pin".into(), cpu.to_string()]);
+        }
+
+        if let Some(max_rsd) = self.max_rsd {
+            args.extend(["--max-rsd".into(), max_rsd.to_string()]);
+        }
+
+        args
+    }
+}
+
+/// A solution part: a function of the input that may also take the [`Context`].
+///
+/// The marker `M` only tells the two kinds of functions apart, it is always inferred.
+pub trait Part<I, M> {
+    type Output;
+
+    fn solve(&self, input: I, ctx: &Context) -> Self::Output;
+}
+
+impl<I, T, F: Fn(I) -> T> Part<I, fn(I) -> T> for F {
+    type Output = T;
+
+    fn solve(&self, input: I, _: &Context) -> T {
+        self(input)
+    }
+}
+
+impl<I, T, F: Fn(I, &Context) -> T> Part<I, fn(I, &Context) -> T> for F {
+    type Output = T;
+
+    fn solve(&self, input: I, ctx: &Context) -> T {
+        self(input, ctx)
+    }
+}
+
+/// The return value of a solution part: an `Option`, where `None` means the part is not
+/// implemented, or a `Result`, where an error means the part failed on its input.
+pub trait IntoAnswer {
+    type Answer: Display;
+
+    /// Converts the value into the answer, `Ok(None)` if the part is not implemented.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error message if the part failed.
+    fn into_answer(self) -> Result<Option<Self::Answer>, String>;
+}
+
+impl<T: Display> IntoAnswer for Option<T> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<Option<T>, String> {
+        Ok(self)
+    }
+}
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+impl<T: Display, E: Display> IntoAnswer for Result<T, E> {
+    type Answer = T;
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    fn into_answer(self) -> Result<Option<T>, String> {
+        self.map(Some).map_err(|e| e.to_string())
+    }
+}
+
+/// The return value of the `parse` function of a two-phase solution: a `Result`, where an
+/// error means the input could not be parsed and fails all parts.
+pub trait IntoParsed {
+    type Parsed;
+
+    /// Converts the value into the parsed input.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error message if the input could not be parsed.
+    fn into_parsed(self) -> Result<Self::Parsed, String>;
+}
+
+impl<T, E: Display> IntoParsed for Result<T, E> {
+    type Parsed = T;
+
+    fn into_parsed(self) -> Result<T, String> {
+        self.map_err(|e| e.to_string())
+    }
+}
+
+pub fn run_part<I: Copy, R: IntoAnswer, M>(
+    func: impl Part<I, M, Output = R>,
+    input: I,
+    ctx: &Context,
+    day: Day,
+    variant: Option<&str>,
+    part: u8,
+) {
+    let part_str = report::part_label(part);
+    let func = |input| func.solve(input, ctx).into_answer();
+
+    let (result, measurement) = run_timed(
+        func,
+        input,
+        || limits::watch(day, variant, part),
+        |result| print_result(result, &part_str, ""),
+    );
+
+    print_result(&result, &part_str, &format_duration(&measurement));
+    print_measurement(&measurement);
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    let record = PartReport {
+        day,
+        variant: variant.map(Into::into),
+        part,
+        answer: result
+            .as_ref()
+            .ok()
+            .and_then(Option::as_ref)
+            .map(ToString::to_string),
+        nanos: measurement.duration.as_nanos() as f64,
+        samples: measurement.samples(),
+        status: match &result {
+            Ok(Some(_)) => Status::Solved,
+            Ok(None) => Status::Unsolved,
+            Err(_) => Status::Failed,
+        },
+        error: result.as_ref().err().cloned(),
+        stats: measurement.stats,
+        alloc: measurement.alloc,
+        counters: measurement.counters.and_then(Result::ok),
+    };
+
+    if let Err(e) = report::emit(&record) {
+        eprintln!("Failed to write report for part {part}: {e}");
+    }
+
+    // Answers to examples are never submitted.
+    if ctx.kind == InputKind::Real
+        && let Ok(Some(result)) = result
+        && let Some(Ok(aoc_client::Verdict::Correct)) = submit_result(&result, day, part)
+    {
+        store_answer(day, part, &result.to_string());
+    }
+}
+
+/// Runs the `parse` function of a two-phase solution, timed like a part and reported as
+/// [`report::PARSE`]. Returns the parsed input, `None` if it could not be parsed.
+pub fn run_parse<I: Copy, R: IntoParsed, M>(
+    func: impl Part<I, M, Output = R>,
+    input: I,
+    ctx: &Context,
+    day: Day,
+    variant: Option<&str>,
+) -> Option<R::Parsed> {
+    let part_str = report::part_label(report::PARSE);
+    let func = |input| func.solve(input, ctx).into_parsed();
+    // NOTE: a parsed input has no answer, it is printed as a check mark.
+    let printable = |result: &Result<R::Parsed, String>| {
+        result.as_ref().map(|_| Some("✔")).map_err(Clone::clone)
+    };
+
+    let (result, measurement) = run_timed(
+        func,
+        input,
+        || limits::watch(day, variant, report::PARSE),
+        |result| print_result(&printable(result), &part_str, ""),
+    );
+
+    print_result(
+        &printable(&result),
+        &part_str,
+        &format_duration(&measurement),
+    );
+    print_measurement(&measurement);
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    let record = PartReport {
+        day,
+        variant: variant.map(Into::into),
+        part: report::PARSE,
+        answer: None,
+        nanos: measurement.duration.as_nanos() as f64,
+        samples: measurement.samples(),
+        status: if result.is_ok() {
+            Status::Solved
+        } else {
+            Status::Failed
+        },
+        error: result.as_ref().err().cloned(),
+        stats: measurement.stats,
+        alloc: measurement.alloc,
+        counters: measurement.counters.and_then(Result::ok),
+    };
+
+    if let Err(e) = report::emit(&record) {
+        eprintln!("Failed to write report for {part_str}: {e}");
+    }
+
+    result.ok()
+}
+
+/// Runs a part of a two-phase solution on the input parsed by [`run_parse`].
+/// If the input could not be parsed, the part fails without running.
+pub fn run_parsed_part<'a, T, R: IntoAnswer, M>(
+    func: impl Part<&'a T, M, Output = R>,
+    parsed: Option<&'a T>,
+    ctx: &Context,
+    day: Day,
+    variant: Option<&str>,
+    part: u8,
+) {
+    if let Some(parsed) = parsed {
+        run_part(func, parsed, ctx, day, variant, part);
+        return;
+    }
+
+    let error = "the input could not be parsed".to_string();
+    println!("{}: ✖ failed\n{error}", report::part_label(part));
+
+    let record = PartReport {
+        day,
+        variant: variant.map(Into::into),
+        part,
+        answer: None,
+        nanos: 0.0,
+        samples: 1,
+        status: Status::Failed,
+        error: Some(error),
+        stats: None,
+        alloc: None,
+        counters: None,
+    };
+
+    if let Err(e) = report::emit(&record) {
+        eprintln!("Failed to write report for part {part}: {e}");
+    }
+}
+
+/// What was measured while running a part, see [`run_timed`].
+struct Measurement {
+    /// The median of all samples when benched, otherwise the duration of the single run.
+    duration: Duration,
+    /// Sample statistics, present when benched.
+    stats: Option<Stats>,
+    /// How often the benchmark was repeated because its samples varied too much.
+    reruns: usize,
+    /// Whether the samples still varied more than `--max-rsd` allows after all re-runs.
+    unstable: bool,
+    alloc: Option<AllocStats>,
+    /// Hardware counters per iteration, or why they are unavailable, present when benched
+    /// with `--counters`.
+    counters: Option<Result<Counters, String>>,
+}
+
+impl Measurement {
+    fn samples(&self) -> u64 {
+        self.stats.as_ref().map_or(1, |s| s.samples)
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched after a warm-up (approx. 1 second of execution time or 10 samples, whatever takes longer).
+///
+/// When benched, the reported duration is the median of all samples. With `--counters`,
+/// the hardware counters are read in an extra pass, see [`count`]. With `--pin`, the
+/// thread is pinned to a CPU before the first execution.
+///
+/// Every execution is watched for exceeding the limits of the part. The allocations of the
+/// first one are counted.
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    watch: impl FnOnce() -> Option<limits::Guard>,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Measurement) {
+    let args: Vec<String> = env::args().collect();
+    let config = args
+        .iter()
+        .any(|x| x == "--time")
+        .then(|| BenchConfig::from_args(&args));
+
+    if let Some(cpu) = config.as_ref().and_then(|config| config.pin) {
+        pin(cpu);
+    }
+
+    let guard = watch();
+    let func = |input| {
+        if let Some(guard) = &guard {
+            guard.next_run();
+        }
+        func(input)
+    };
+
+    let tracker = alloc::track();
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -38,63 +404,201 @@ fn run_timed<I: Copy, T>(
         func(input)
     };
     let base_time = timer.elapsed();
+    let alloc = tracker.map(alloc::Tracker::finish);
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
+    let Some(config) = config else {
+        let measurement = Measurement {
+            duration: base_time,
+            stats: None,
+            reruns: 0,
+            unstable: false,
+            alloc,
+            counters: None,
+        };
+        return (result, measurement);
+    };
+
+    let (stats, reruns) = bench(func, input, &config);
+    let unstable = config
+        .max_rsd
+        .is_some_and(|max_rsd| stats.relative_std_dev() * 100.0 > f64::from(max_rsd));
+    let counters = config.counters.then(|| count(func, input, &config, &stats));
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let measurement = Measurement {
+        duration: Duration::from_nanos(stats.median as u64),
+        stats: Some(stats),
+        reruns,
+        unstable,
+        alloc,
+        counters,
     };
 
-    (result, run.0, run.1)
+    (result, measurement)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Pins the process to `cpu` for all parts it runs. A failure is reported once, and the
+/// parts are benched unpinned.
+fn pin(cpu: usize) {
+    static PINNED: Once = Once::new();
+
+    PINNED.call_once(|| {
+        if let Err(e) = environment::pin_to_cpu(cpu) {
+            eprintln!("{ANSI_ITALIC}Benching unpinned, {e}.{ANSI_RESET}");
+        }
+    });
+}
+
+/// Benches the function, repeating the benchmark while its samples vary by more than
+/// `max_rsd`, at most [`MAX_RERUNS`] times. Returns the statistics of the least varying
+/// benchmark, and the number of re-runs.
+fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, config: &BenchConfig) -> (Stats, usize) {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let mut stats = sample(&func, input, config);
+    let mut reruns = 0;
+
+    while let Some(max_rsd) = config.max_rsd
+        && stats.relative_std_dev() * 100.0 > f64::from(max_rsd)
+        && reruns < MAX_RERUNS
+    {
+        let rerun = sample(&func, input, config);
+        if rerun.relative_std_dev() < stats.relative_std_dev() {
+            stats = rerun;
+        }
+        reruns += 1;
+    }
+
+    (stats, reruns)
+}
+
+/// Collects the samples of a benchmark, after a warm-up.
+fn sample<I: Copy, T>(func: impl Fn(I) -> T, input: I, config: &BenchConfig) -> Stats {
+    // Warm up caches, branch predictors and CPU clocks before measuring.
+    let warm_up = Instant::now();
+    while warm_up.elapsed() < config.warm_up {
+        black_box(func(black_box(input)));
+    }
 
     let mut timers: Vec<Duration> = vec![];
+    let start = Instant::now();
 
-    for _ in 0..bench_iterations {
+    while timers.len() < MIN_SAMPLES
+        || (start.elapsed() < config.target_time && timers.len() < MAX_SAMPLES)
+    {
         let timer = Instant::now();
         black_box(func(black_box(input)));
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    // NOTE: at least MIN_SAMPLES samples have been collected.
+    Stats::from_samples(&timers).unwrap()
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Reads the hardware counters over a pass of at most as many iterations as were sampled,
+/// taking approx. a quarter of the target time. Counters are read separately from the
+/// samples, so that reading them does not distort the timings.
+///
+/// # Errors
+///
+/// Returns the reason if no counter is available.
+fn count<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    config: &BenchConfig,
+    stats: &Stats,
+) -> Result<Counters, String> {
+    let group = CounterGroup::open()?;
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let iterations = ((config.target_time.as_nanos() as f64 / 4.0 / stats.median.max(1.0)) as u64)
+        .clamp(1, stats.samples);
+
+    group.enable();
+    for _ in 0..iterations {
+        black_box(func(black_box(input)));
+    }
+    group.disable();
+
+    Ok(group.read(iterations))
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
+fn format_duration(measurement: &Measurement) -> String {
+    let Measurement {
+        duration, alloc, ..
+    } = measurement;
+    let samples = measurement.samples();
+    let alloc = alloc.map_or_else(String::new, |alloc| format!(" · {}", alloc.summary()));
+
     if samples == 1 {
-        format!(" ({duration:.1?})")
+        format!(" ({duration:.1?}{alloc})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({duration:.1?} @ {samples} samples{alloc})")
+    }
+}
+
+fn print_measurement(measurement: &Measurement) {
+    if let Some(stats) = &measurement.stats {
+        print_stats(stats, measurement.reruns);
+    }
+
+    if measurement.unstable {
+        println!(
+            "  {ANSI_ITALIC}unstable: σ is above `--max-rsd` after {MAX_RERUNS} re-runs{ANSI_RESET}"
+        );
+    }
+
+    // NOTE: unavailable counters stay unavailable, they are only reported for the first part.
+    static UNAVAILABLE: Once = Once::new();
+
+    match &measurement.counters {
+        Some(Ok(counters)) => {
+            let summary = counters.summary();
+            if !summary.is_empty() {
+                println!("  {ANSI_ITALIC}{summary}{ANSI_RESET}");
+            }
+        }
+        Some(Err(e)) => UNAVAILABLE.call_once(|| {
+            println!("  {ANSI_ITALIC}hardware counters are unavailable: {e}{ANSI_RESET}");
+        }),
+        None => {}
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn print_stats(stats: &Stats, reruns: usize) {
+    let fmt = |nanos: f64| format!("{:.1?}", Duration::from_nanos(nanos as u64));
+    let reruns = match reruns {
+        0 => String::new(),
+        1 => " · 1 re-run".into(),
+        n => format!(" · {n} re-runs"),
+    };
+
+    println!(
+        "  {ANSI_ITALIC}min {} · median {} · mean {} · p95 {} · σ {} · {} outliers{reruns}{ANSI_RESET}",
+        fmt(stats.min),
+        fmt(stats.median),
+        fmt(stats.mean),
+        fmt(stats.p95),
+        fmt(stats.std_dev),
+        stats.outliers
+    );
+}
+
+fn print_result<T: Display>(result: &Result<Option<T>, String>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Ok(Some(result)) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -114,25 +618,54 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Ok(None) => {
+            let str = format!("{part}: {ANSI_ITALIC}not implemented{ANSI_RESET}");
             if is_intermediate_result {
-                print!("{part}: ✖");
+                print!("{str}");
             } else {
                 print!("\r");
-                println!("{part}: ✖             ");
+                println!("{str}             ");
             }
         }
+        Err(error) => {
+            let str = format!("{part}: ✖ failed{duration_str}");
+            if is_intermediate_result {
+                print!("{str}");
+            } else {
+                print!("\r");
+                println!("{str}");
+                println!("{error}");
+            }
+        }
+    }
+}
+
+/// Records an accepted answer in `data/answers.json`, for later use by `cargo verify`.
+fn store_answer(day: Day, part: u8, answer: &str) {
+    let mut answers = match Answers::read_from_file(day.event()) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Not storing accepted answer, {e}");
+            return;
+        }
+    };
+    answers.set(day, part, answer);
+
+    match answers.store_file(day.event()) {
+        Ok(()) => println!("Stored accepted answer for part {part}."),
+        Err(e) => eprintln!("Failed to store accepted answer: {e}"),
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is configured.
+///  3. the submission log does not already rule out the answer.
 fn submit_result<T: Display>(
-    result: T,
+    result: &T,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<aoc_client::Verdict, aoc_client::AocError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -155,13 +688,33 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
-        );
-        process::exit(1);
+    let answer = result.to_string();
+    let mut log = match Submissions::read_from_file(day.event()) {
+        Ok(log) => log,
+        Err(e) => {
+            eprintln!("Not submitting {answer}, {e}");
+            return None;
+        }
+    };
+
+    if let Err(refusal) = log.check(day, part, &answer, clock::now()) {
+        eprintln!("Not submitting {answer}: {refusal}");
+        return None;
+    }
+
+    println!("Submitting result...");
+    let verdict = aoc_client::submit(day, part, &answer);
+
+    match &verdict {
+        Ok(verdict) => {
+            log.record(day, part, &answer, verdict.clone(), clock::now());
+
+            if let Err(e) = log.store_file(day.event()) {
+                eprintln!("Failed to store submission log: {e}");
+            }
+        }
+        Err(e) => eprintln!("Failed to submit result: {e}"),
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(verdict)
 }
@@ -0,0 +1,399 @@
+//! Scaling benchmarks: timings of a day's parts over inputs of increasing size.
+//!
+//! The inputs come from the day's [generator](crate::generators) or from `data/generated/`.
+//! An empirical exponent `k` is fitted to `time ≈ c · size^k` by least squares on the
+//! logarithms of the measurements, so that `k ≈ 2` backs a claim of `O(n²)`.
+use std::{collections::HashMap, fs, io, path::PathBuf, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    Day, day::day_to_json, layout, report::part_label, run_multi::label, stats::Stats,
+};
+
+static SCALING_DIR_NAME: &str = "scaling";
+
+/// Multiples of a generator's default size that are benched unless sizes are given.
+const SIZE_FACTORS: [f64; 6] = [0.125, 0.25, 0.5, 1.0, 2.0, 4.0];
+
+const PLOT_WIDTH: usize = 60;
+const PLOT_HEIGHT: usize = 16;
+const PLOT_MARKERS: [char; 6] = ['●', '○', '▲', '△', '■', '□'];
+
+/// How the inputs of a scaling benchmark are picked.
+#[derive(Debug, Default)]
+pub struct ScaleConfig {
+    /// The sizes to generate, see [`default_sizes`] if not given.
+    pub sizes: Option<Vec<usize>>,
+    pub seed: u64,
+    /// Use the inputs in `data/generated/` instead of generating them.
+    pub from_files: bool,
+    /// Print a log-log plot of the timings.
+    pub plot: bool,
+}
+
+/// Returns sizes from an eighth to four times the given default size.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub fn default_sizes(default_size: usize) -> Vec<usize> {
+    let mut sizes: Vec<usize> = SIZE_FACTORS
+        .iter()
+        .map(|factor| ((default_size as f64 * factor).round() as usize).max(1))
+        .collect();
+    sizes.dedup();
+    sizes
+}
+
+/// The timing of a part at one input size.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Point {
+    pub size: usize,
+    pub stats: Stats,
+}
+
+/// The timings of a part over all input sizes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Series {
+    pub variant: Option<String>,
+    pub part: u8,
+    pub points: Vec<Point>,
+}
+
+impl Series {
+    /// Returns the exponent `k` of `time ≈ c · size^k`, see [`fit_exponent`].
+    #[allow(clippy::cast_precision_loss)]
+    pub fn exponent(&self) -> Option<f64> {
+        let points: Vec<(f64, f64)> = self
+            .points
+            .iter()
+            .map(|point| (point.size as f64, point.stats.median))
+            .collect();
+        fit_exponent(&points)
+    }
+
+    fn label(&self, day: Day) -> String {
+        format!(
+            "{} {}",
+            label(day, self.variant.as_deref()),
+            part_label(self.part)
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ScalingReport {
+    pub day: Day,
+    /// What the size counts, e.g. `junction boxes`.
+    pub unit: String,
+    pub series: Vec<Series>,
+}
+
+impl ScalingReport {
+    pub fn new(day: Day, unit: &str) -> Self {
+        Self {
+            day,
+            unit: unit.into(),
+            series: vec![],
+        }
+    }
+
+    /// Adds the timing of a part at one input size.
+    pub fn add(&mut self, variant: Option<&str>, part: u8, point: Point) {
+        let position = self
+            .series
+            .iter()
+            .position(|s| s.variant.as_deref() == variant && s.part == part);
+
+        match position {
+            Some(i) => self.series[i].points.push(point),
+            None => self.series.push(Series {
+                variant: variant.map(Into::into),
+                part,
+                points: vec![point],
+            }),
+        }
+    }
+
+    /// Stores the report as `data/scaling/{day}.csv` and `data/scaling/{day}.json`.
+    pub fn store_files(&self) -> io::Result<[PathBuf; 2]> {
+        let dir = layout::data_dir(self.day.event()).join(SCALING_DIR_NAME);
+        fs::create_dir_all(&dir)?;
+
+        let csv_path = dir.join(format!("{}.csv", self.day));
+        fs::write(&csv_path, self.to_csv())?;
+
+        let json_path = dir.join(format!("{}.json", self.day));
+        let mut file = fs::File::create(&json_path)?;
+        JsonValue::from(self).format_to(&mut file)?;
+
+        Ok([csv_path, json_path])
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut lines = vec![
+            "variant,part,size,samples,min_nanos,median_nanos,mean_nanos,p95_nanos,std_dev_nanos"
+                .to_string(),
+        ];
+
+        for series in &self.series {
+            for Point { size, stats } in &series.points {
+                lines.push(format!(
+                    "{},{},{size},{},{},{},{},{},{}",
+                    series.variant.as_deref().unwrap_or_default(),
+                    series.part,
+                    stats.samples,
+                    stats.min,
+                    stats.median,
+                    stats.mean,
+                    stats.p95,
+                    stats.std_dev
+                ));
+            }
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    /// Renders the timings of all parts as a log-log scatter plot, with a legend.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn plot(&self) -> String {
+        let points: Vec<(f64, f64)> = self
+            .series
+            .iter()
+            .flat_map(|series| &series.points)
+            .filter(|point| point.size > 0 && point.stats.median > 0.0)
+            .map(|point| ((point.size as f64).ln(), point.stats.median.ln()))
+            .collect();
+
+        if points.is_empty() {
+            return "No timings to plot.".into();
+        }
+
+        let bounds = |values: &mut dyn Iterator<Item = f64>| {
+            values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+                (min.min(v), max.max(v))
+            })
+        };
+        let (min_x, max_x) = bounds(&mut points.iter().map(|p| p.0));
+        let (min_y, max_y) = bounds(&mut points.iter().map(|p| p.1));
+
+        // a single size or timing is drawn in the middle of the axis.
+        let scale = |v: f64, min: f64, max: f64, len: usize| {
+            let ratio = if max > min {
+                (v - min) / (max - min)
+            } else {
+                0.5
+            };
+            (ratio * (len - 1) as f64).round() as usize
+        };
+
+        let mut grid = vec![vec![' '; PLOT_WIDTH]; PLOT_HEIGHT];
+
+        for (i, series) in self.series.iter().enumerate() {
+            let marker = PLOT_MARKERS[i % PLOT_MARKERS.len()];
+
+            for point in &series.points {
+                if point.size == 0 || point.stats.median <= 0.0 {
+                    continue;
+                }
+
+                let col = scale((point.size as f64).ln(), min_x, max_x, PLOT_WIDTH);
+                let row = scale(point.stats.median.ln(), min_y, max_y, PLOT_HEIGHT);
+                grid[PLOT_HEIGHT - 1 - row][col] = marker;
+            }
+        }
+
+        let fmt = |ln_nanos: f64| format!("{:.1?}", Duration::from_nanos(ln_nanos.exp() as u64));
+
+        let mut lines: Vec<String> = grid
+            .iter()
+            .enumerate()
+            .map(|(row, cells)| {
+                let label = match row {
+                    0 => fmt(max_y),
+                    r if r == PLOT_HEIGHT - 1 => fmt(min_y),
+                    _ => String::new(),
+                };
+                format!("{label:>10} │{}", cells.iter().collect::<String>())
+            })
+            .collect();
+
+        lines.push(format!("{:>10} └{}", "", "─".repeat(PLOT_WIDTH)));
+
+        let min_size = min_x.exp().round().to_string();
+        let max_size = format!("{} {}", max_x.exp().round(), self.unit);
+        lines.push(format!(
+            "{:>10}  {min_size}{max_size:>width$}",
+            "",
+            width = PLOT_WIDTH - min_size.len()
+        ));
+
+        lines.push(String::new());
+
+        for (i, series) in self.series.iter().enumerate() {
+            let exponent = series
+                .exponent()
+                .map_or_else(|| "-".into(), |k| format!("{k:.2}"));
+            lines.push(format!(
+                "{} {} (k = {exponent})",
+                PLOT_MARKERS[i % PLOT_MARKERS.len()],
+                series.label(self.day)
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Fits `y ≈ c · x^k` to the points by least squares on `ln y = ln c + k · ln x`, returning `k`.
+/// Points with non-positive coordinates are ignored. Returns `None` without two distinct `x`.
+#[allow(clippy::cast_precision_loss)]
+pub fn fit_exponent(points: &[(f64, f64)]) -> Option<f64> {
+    let logs: Vec<(f64, f64)> = points
+        .iter()
+        .filter(|(x, y)| *x > 0.0 && *y > 0.0)
+        .map(|(x, y)| (x.ln(), y.ln()))
+        .collect();
+
+    if logs.len() < 2 {
+        return None;
+    }
+
+    let n = logs.len() as f64;
+    let mean_x = logs.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = logs.iter().map(|p| p.1).sum::<f64>() / n;
+
+    let s_xx: f64 = logs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let s_xy: f64 = logs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+
+    (s_xx > 0.0).then(|| s_xy / s_xx)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&ScalingReport> for JsonValue {
+    fn from(value: &ScalingReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        day_to_json(&mut map, value.day);
+        map.insert("unit".into(), JsonValue::String(value.unit.clone()));
+        map.insert(
+            "series".into(),
+            JsonValue::Array(value.series.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&Series> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Series) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "variant".into(),
+            value
+                .variant
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "exponent".into(),
+            value.exponent().map_or(JsonValue::Null, JsonValue::Number),
+        );
+
+        let points = value
+            .points
+            .iter()
+            .map(|point| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                map.insert("size".into(), JsonValue::Number(point.size as f64));
+                map.insert("stats".into(), JsonValue::from(&point.stats));
+                JsonValue::Object(map)
+            })
+            .collect();
+        map.insert("points".into(), JsonValue::Array(points));
+
+        JsonValue::Object(map)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Point, ScalingReport, default_sizes, fit_exponent};
+    use crate::{day, template::stats::Stats};
+
+    fn point(size: usize, median: f64) -> Point {
+        Point {
+            size,
+            stats: Stats {
+                samples: 10,
+                min: median,
+                median,
+                mean: median,
+                p95: median,
+                std_dev: 0.0,
+                outliers: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn fits_exponents() {
+        let quadratic: Vec<(f64, f64)> = (1..=5)
+            .map(|i| (f64::from(i) * 100.0, 3.0 * (f64::from(i) * 100.0).powi(2)))
+            .collect();
+        assert!((fit_exponent(&quadratic).unwrap() - 2.0).abs() < 1e-9);
+
+        let constant = [(10.0, 5.0), (20.0, 5.0), (40.0, 5.0)];
+        assert!(fit_exponent(&constant).unwrap().abs() < 1e-9);
+
+        assert_eq!(fit_exponent(&[(10.0, 1.0)]), None);
+        assert_eq!(fit_exponent(&[(10.0, 1.0), (10.0, 2.0)]), None);
+    }
+
+    #[test]
+    fn groups_points_into_series() {
+        let mut report = ScalingReport::new(day!(8), "junction boxes");
+        report.add(None, 1, point(100, 1e3));
+        report.add(None, 2, point(100, 2e3));
+        report.add(None, 1, point(200, 4e3));
+        report.add(Some("fast"), 1, point(100, 1e2));
+
+        assert_eq!(report.series.len(), 3);
+        assert_eq!(report.series[0].points.len(), 2);
+        assert!((report.series[0].exponent().unwrap() - 2.0).abs() < 1e-9);
+
+        let csv = report.to_csv();
+        assert_eq!(csv.lines().count(), 5);
+        assert!(csv.contains("\nfast,1,100,10,100,100,100,100,0\n"));
+    }
+
+    #[test]
+    fn plots_all_series() {
+        let mut report = ScalingReport::new(day!(8), "junction boxes");
+        report.add(None, 1, point(100, 1e3));
+        report.add(None, 1, point(1000, 1e5));
+
+        let plot = report.plot();
+        assert_eq!(plot.matches('●').count(), 3);
+        assert!(plot.contains("● Day 08 Part 1 (k = 2.00)"));
+        assert!(plot.contains("1000 junction boxes"));
+    }
+
+    #[test]
+    fn spans_default_sizes() {
+        assert_eq!(default_sizes(1000), vec![125, 250, 500, 1000, 2000, 4000]);
+        assert_eq!(default_sizes(4), vec![1, 2, 4, 8, 16]);
+    }
+}
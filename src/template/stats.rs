@@ -0,0 +1,256 @@
+//! Summary statistics over a set of benchmark samples.
+use std::{collections::HashMap, time::Duration};
+
+use tinyjson::JsonValue;
+
+/// Relative change of the median below which a difference is treated as noise.
+const NOISE_THRESHOLD: f64 = 0.03;
+
+/// Critical value of Welch's t-test, for a one-sided significance level of about 1%.
+const T_CRITICAL: f64 = 2.33;
+
+/// The outcome of comparing a benchmark against a baseline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Change {
+    Regression,
+    Improvement,
+    NoChange,
+}
+
+/// Descriptive statistics of benchmark samples, in nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub samples: u64,
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub p95: f64,
+    pub std_dev: f64,
+    /// Number of samples outside the Tukey fences (1.5 × IQR beyond the quartiles).
+    pub outliers: u64,
+}
+
+impl Stats {
+    /// Computes statistics from the given samples. Returns `None` if there are none.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        #[allow(clippy::cast_precision_loss)]
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+
+        if nanos.is_empty() {
+            return None;
+        }
+
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        #[allow(clippy::cast_precision_loss)]
+        let n = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / n;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+
+        let q1 = percentile(&nanos, 0.25);
+        let q3 = percentile(&nanos, 0.75);
+        let iqr = q3 - q1;
+        let (lower, upper) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        Some(Self {
+            samples: nanos.len() as u64,
+            min: nanos[0],
+            median: percentile(&nanos, 0.5),
+            mean,
+            p95: percentile(&nanos, 0.95),
+            std_dev: variance.sqrt(),
+            outliers: nanos.iter().filter(|&&x| x < lower || x > upper).count() as u64,
+        })
+    }
+
+    /// Standard deviation relative to the mean, e.g. `0.05` if the samples vary by 5%.
+    pub fn relative_std_dev(&self) -> f64 {
+        if self.mean > 0.0 {
+            self.std_dev / self.mean
+        } else {
+            0.0
+        }
+    }
+
+    /// Relative change of the median compared to a `baseline`, e.g. `0.1` for 10% slower.
+    pub fn relative_change(&self, baseline: &Stats) -> f64 {
+        self.median / baseline.median - 1.0
+    }
+
+    /// Compares these statistics against a `baseline`.
+    ///
+    /// A change is significant if Welch's t-test rejects equal means and the medians
+    /// differ by more than [`NOISE_THRESHOLD`], so that tiny differences measured over
+    /// many samples are not flagged.
+    pub fn compare(&self, baseline: &Stats) -> Change {
+        #[allow(clippy::cast_precision_loss)]
+        let std_err = (self.std_dev.powi(2) / self.samples as f64
+            + baseline.std_dev.powi(2) / baseline.samples as f64)
+            .sqrt();
+
+        let diff = self.mean - baseline.mean;
+        let t = if std_err > 0.0 {
+            diff / std_err
+        } else {
+            diff.signum() * f64::INFINITY
+        };
+
+        let change = self.relative_change(baseline);
+
+        if t > T_CRITICAL && change > NOISE_THRESHOLD {
+            Change::Regression
+        } else if t < -T_CRITICAL && change < -NOISE_THRESHOLD {
+            Change::Improvement
+        } else {
+            Change::NoChange
+        }
+    }
+}
+
+/// Linearly interpolated percentile of a sorted, non-empty slice, with `p` in `0..=1`.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let rank = p * (sorted.len() - 1) as f64;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - rank.floor())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+            map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+        }
+
+        map.insert("min_nanos".into(), JsonValue::Number(value.min));
+        map.insert("median_nanos".into(), JsonValue::Number(value.median));
+        map.insert("mean_nanos".into(), JsonValue::Number(value.mean));
+        map.insert("p95_nanos".into(), JsonValue::Number(value.p95));
+        map.insert("std_dev_nanos".into(), JsonValue::Number(value.std_dev));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Stats {
+            samples: number("samples")? as u64,
+            min: number("min_nanos")?,
+            median: number("median_nanos")?,
+            mean: number("mean_nanos")?,
+            p95: number("p95_nanos")?,
+            std_dev: number("std_dev_nanos")?,
+            outliers: number("outliers")? as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Change, Stats, percentile};
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn computes_summary() {
+        let stats = Stats::from_samples(&nanos(&[5, 1, 4, 2, 3])).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.median, 3.0);
+        assert_eq!(stats.mean, 3.0);
+        assert_eq!(stats.std_dev, 2_f64.sqrt());
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn interpolates_percentiles() {
+        let sorted = [10.0, 20.0, 30.0, 40.0];
+        assert_eq!(percentile(&sorted, 0.0), 10.0);
+        assert_eq!(percentile(&sorted, 0.5), 25.0);
+        assert_eq!(percentile(&sorted, 1.0), 40.0);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = Stats::from_samples(&nanos(&[100, 101, 99, 100, 102, 98, 100, 5000])).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.median, 100.0);
+        assert!(stats.mean > 600.0);
+    }
+
+    fn stats(median: f64, std_dev: f64, samples: u64) -> Stats {
+        Stats {
+            samples,
+            min: median,
+            median,
+            mean: median,
+            p95: median,
+            std_dev,
+            outliers: 0,
+        }
+    }
+
+    #[test]
+    fn flags_significant_changes() {
+        let baseline = stats(100.0, 10.0, 1000);
+        assert_eq!(
+            stats(120.0, 10.0, 1000).compare(&baseline),
+            Change::Regression
+        );
+        assert_eq!(
+            stats(80.0, 10.0, 1000).compare(&baseline),
+            Change::Improvement
+        );
+    }
+
+    #[test]
+    fn ignores_noise() {
+        let baseline = stats(100.0, 10.0, 1000);
+        // significant, but below the noise threshold.
+        assert_eq!(
+            stats(101.0, 1.0, 100_000).compare(&baseline),
+            Change::NoChange
+        );
+        // large, but not significant.
+        assert_eq!(stats(120.0, 100.0, 10).compare(&baseline), Change::NoChange);
+    }
+
+    #[test]
+    fn relates_deviation_to_mean() {
+        assert_eq!(stats(200.0, 10.0, 100).relative_std_dev(), 0.05);
+        assert_eq!(stats(0.0, 0.0, 100).relative_std_dev(), 0.0);
+    }
+}
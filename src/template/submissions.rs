@@ -0,0 +1,428 @@
+//! Local log of submitted answers, used to avoid re-submitting answers that are
+//! already known to be wrong and to respect the server's cooldown.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+    time::Duration,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    Day, Event,
+    aoc_client::{Hint, Verdict},
+    day::{day_from_json, day_to_json},
+    layout,
+};
+
+static SUBMISSIONS_FILE_NAME: &str = "submissions";
+
+/// The server makes you wait at least a minute after a wrong answer, and longer after
+/// repeated ones, which then answer with a [`Verdict::TooRecent`] that carries the wait.
+const INCORRECT_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// A single submitted answer and the server's verdict.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+/// Represents all submissions made so far.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+/// Reason for refusing to submit an answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    KnownWrong,
+    TooHigh(String),
+    TooLow(String),
+    Cooldown(Duration),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "this part is already solved with answer {answer}.")
+            }
+            Refusal::KnownWrong => write!(f, "this answer was already rejected."),
+            Refusal::TooHigh(bound) => {
+                write!(f, "answers of {bound} or more are known to be too high.")
+            }
+            Refusal::TooLow(bound) => {
+                write!(f, "answers of {bound} or less are known to be too low.")
+            }
+            Refusal::Cooldown(wait) => {
+                write!(
+                    f,
+                    "submitted too recently, wait another {}s.",
+                    wait.as_secs()
+                )
+            }
+        }
+    }
+}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self, event: Event) -> Result<(), Error> {
+        let path = layout::event_file(event, SUBMISSIONS_FILE_NAME);
+        fs::create_dir_all(layout::data_dir(event))?;
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns an empty log.
+    ///
+    /// # Errors
+    ///
+    /// Returns the reason if the file exists but cannot be read or parsed. Callers must not
+    /// store the log over such a file, as that would discard the submissions it holds.
+    pub fn read_from_file(event: Event) -> Result<Self, String> {
+        let path = layout::event_file(event, SUBMISSIONS_FILE_NAME);
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => Submissions::try_from(contents)
+                .map_err(|e| format!("{} is malformed: {e}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(e) => Err(format!("could not read {}: {e}", path.display())),
+        }
+    }
+
+    /// Checks whether an answer may be submitted at time `now` (seconds since the Unix epoch).
+    pub fn check(&self, day: Day, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        let previous = || {
+            self.data
+                .iter()
+                .filter(move |s| s.day == day && s.part == part)
+        };
+
+        if let Some(correct) = previous().find(|s| s.verdict == Verdict::Correct) {
+            return Err(Refusal::AlreadySolved(correct.answer.clone()));
+        }
+
+        if previous().any(|s| s.answer == answer && matches!(s.verdict, Verdict::Incorrect(_))) {
+            return Err(Refusal::KnownWrong);
+        }
+
+        if let Ok(value) = answer.trim().parse::<i128>() {
+            let bound = |hint| {
+                previous()
+                    .filter(move |s| s.verdict == Verdict::Incorrect(Some(hint)))
+                    .filter_map(|s| s.answer.trim().parse::<i128>().ok())
+            };
+
+            if let Some(high) = bound(Hint::TooHigh).min().filter(|&high| value >= high) {
+                return Err(Refusal::TooHigh(high.to_string()));
+            }
+
+            if let Some(low) = bound(Hint::TooLow).max().filter(|&low| value <= low) {
+                return Err(Refusal::TooLow(low.to_string()));
+            }
+        }
+
+        // NOTE: the server's cooldown applies to the whole account, not just this part.
+        let wait_until = self.data.iter().filter_map(Submission::wait_until).max();
+
+        match wait_until {
+            Some(until) if until > now => Err(Refusal::Cooldown(Duration::from_secs(until - now))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Records a submission and its verdict.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, verdict: Verdict, now: u64) {
+        self.data.push(Submission {
+            day,
+            part,
+            answer: answer.into(),
+            verdict,
+            timestamp: now,
+        });
+    }
+}
+
+impl Submission {
+    /// Returns until when the server refuses further answers after this submission, in
+    /// seconds since the Unix epoch.
+    fn wait_until(&self) -> Option<u64> {
+        let wait = match self.verdict {
+            Verdict::TooRecent(Some(wait)) => wait,
+            // NOTE: assume the shortest cooldown if the server's wait could not be parsed.
+            Verdict::Incorrect(_) | Verdict::TooRecent(None) => INCORRECT_COOLDOWN,
+            _ => return None,
+        };
+
+        Some(self.timestamp + wait.as_secs())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let (verdict, wait, message) = match &value.verdict {
+            Verdict::Correct => ("correct", None, None),
+            Verdict::Incorrect(None) => ("incorrect", None, None),
+            Verdict::Incorrect(Some(Hint::TooHigh)) => ("too_high", None, None),
+            Verdict::Incorrect(Some(Hint::TooLow)) => ("too_low", None, None),
+            Verdict::TooRecent(wait) => ("too_recent", *wait, None),
+            Verdict::WrongLevel => ("wrong_level", None, None),
+            Verdict::Unknown(message) => ("unknown", None, Some(message)),
+        };
+
+        day_to_json(&mut map, value.day);
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert("verdict".into(), JsonValue::String(verdict.into()));
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "wait_secs".into(),
+            match wait {
+                Some(x) => JsonValue::Number(x.as_secs() as f64),
+                None => JsonValue::Null,
+            },
+        );
+
+        if let Some(message) = message {
+            map.insert("message".into(), JsonValue::String(message.clone()));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = day_from_json(json).ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|&p| p as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|&t| t as u64)
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        let wait = json
+            .get("wait_secs")
+            .and_then(|v| v.get::<f64>())
+            .map(|&w| Duration::from_secs(w as u64));
+
+        let verdict = match json.get("verdict").and_then(|v| v.get::<String>()) {
+            Some(v) if v == "correct" => Verdict::Correct,
+            Some(v) if v == "incorrect" => Verdict::Incorrect(None),
+            Some(v) if v == "too_high" => Verdict::Incorrect(Some(Hint::TooHigh)),
+            Some(v) if v == "too_low" => Verdict::Incorrect(Some(Hint::TooLow)),
+            Some(v) if v == "too_recent" => Verdict::TooRecent(wait),
+            Some(v) if v == "wrong_level" => Verdict::WrongLevel,
+            Some(v) if v == "unknown" => Verdict::Unknown(
+                json.get("message")
+                    .and_then(|v| v.get::<String>())
+                    .cloned()
+                    .unwrap_or_default(),
+            ),
+            _ => return Err("Expected submission.verdict to be a known verdict.".into()),
+        };
+
+        Ok(Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            verdict,
+            timestamp,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{Refusal, Submissions};
+    use crate::{
+        day,
+        template::aoc_client::{Hint, Verdict},
+    };
+
+    fn get_mock_submissions() -> Submissions {
+        let mut log = Submissions::default();
+        log.record(
+            day!(1),
+            1,
+            "100",
+            Verdict::Incorrect(Some(Hint::TooHigh)),
+            0,
+        );
+        log.record(
+            day!(1),
+            1,
+            "10",
+            Verdict::Incorrect(Some(Hint::TooLow)),
+            100,
+        );
+        log.record(day!(1), 1, "50", Verdict::Incorrect(None), 200);
+        log.record(day!(2), 1, "7", Verdict::Correct, 300);
+        log
+    }
+
+    #[test]
+    fn allows_new_answers_within_bounds() {
+        let log = get_mock_submissions();
+        assert_eq!(log.check(day!(1), 1, "42", 1000), Ok(()));
+        assert_eq!(log.check(day!(1), 2, "100", 1000), Ok(()));
+    }
+
+    #[test]
+    fn refuses_known_answers() {
+        let log = get_mock_submissions();
+        assert_eq!(log.check(day!(1), 1, "50", 1000), Err(Refusal::KnownWrong));
+        assert_eq!(
+            log.check(day!(2), 1, "8", 1000),
+            Err(Refusal::AlreadySolved("7".into()))
+        );
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let log = get_mock_submissions();
+        assert_eq!(
+            log.check(day!(1), 1, "150", 1000),
+            Err(Refusal::TooHigh("100".into()))
+        );
+        assert_eq!(
+            log.check(day!(1), 1, "5", 1000),
+            Err(Refusal::TooLow("10".into()))
+        );
+    }
+
+    #[test]
+    fn refuses_during_cooldown() {
+        let mut log = get_mock_submissions();
+        log.record(
+            day!(1),
+            1,
+            "42",
+            Verdict::TooRecent(Some(Duration::from_secs(60))),
+            1000,
+        );
+
+        assert_eq!(
+            log.check(day!(1), 1, "42", 1045),
+            Err(Refusal::Cooldown(Duration::from_secs(15)))
+        );
+        assert_eq!(log.check(day!(1), 1, "42", 1060), Ok(()));
+    }
+
+    #[test]
+    fn refuses_after_incorrect_answer() {
+        let mut log = get_mock_submissions();
+        log.record(day!(1), 2, "42", Verdict::Incorrect(None), 1000);
+
+        assert_eq!(
+            log.check(day!(1), 2, "43", 1020),
+            Err(Refusal::Cooldown(Duration::from_secs(40)))
+        );
+        assert_eq!(log.check(day!(1), 2, "43", 1060), Ok(()));
+    }
+
+    #[test]
+    fn round_trips_submissions() {
+        let mut log = get_mock_submissions();
+        log.record(
+            day!(3),
+            2,
+            "5",
+            Verdict::TooRecent(Some(Duration::from_secs(30))),
+            400,
+        );
+        log.record(
+            day!(3),
+            2,
+            "6",
+            Verdict::Unknown("Unexpected response.".into()),
+            500,
+        );
+
+        let json = JsonValue::from(log.clone()).stringify().unwrap();
+        let parsed = Submissions::try_from(json).unwrap();
+        assert_eq!(parsed.data, log.data);
+    }
+}
@@ -1,43 +1,150 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
-
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+use crate::template::{
+    Day, Event,
+    alloc::AllocStats,
+    counters::Counters,
+    day::{day_from_json, day_to_json},
+    environment::Environment,
+    layout, report,
+    stats::Stats,
+};
+
+static TIMINGS_FILE_NAME: &str = "timings";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    /// The variant of the solution, `None` for the main one.
+    pub variant: Option<String>,
+    /// The time taken to parse the input of a two-phase solution, `None` for other solutions.
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// The total time of all parts, including parsing.
     pub total_nanos: f64,
+    pub parse_stats: Option<Stats>,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
+    /// The heap usage of each part, see [`alloc`](crate::template::alloc).
+    pub parse_alloc: Option<AllocStats>,
+    pub part_1_alloc: Option<AllocStats>,
+    pub part_2_alloc: Option<AllocStats>,
+    /// Hardware counters of each part, recorded with `cargo time --counters`,
+    /// see [`counters`](crate::template::counters).
+    pub parse_counters: Option<Counters>,
+    pub part_1_counters: Option<Counters>,
+    pub part_2_counters: Option<Counters>,
+    /// Parts that failed on their input, which have no timing. Part 0 is the parse phase.
+    pub failed: Vec<u8>,
+    /// Parts that exceeded their time limit, see [`limits`](crate::template::limits).
+    pub timed_out: Vec<u8>,
+    /// Parts that exceeded their memory limit.
+    pub out_of_memory: Vec<u8>,
+}
+
+impl Timing {
+    /// Creates the timing of a day without any benchmarked, failed or stopped parts.
+    pub fn new(day: Day) -> Self {
+        Self {
+            day,
+            variant: None,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse_alloc: None,
+            part_1_alloc: None,
+            part_2_alloc: None,
+            parse_counters: None,
+            part_1_counters: None,
+            part_2_counters: None,
+            failed: vec![],
+            timed_out: vec![],
+            out_of_memory: vec![],
+        }
+    }
 }
 
-/// Represents benchmark times for a set of days.
+/// A benchmark of a single part, as kept in the history.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimingRecord {
+    pub day: Day,
+    pub variant: Option<String>,
+    pub part: u8,
+    pub stats: Stats,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The commit that was benched, suffixed with `-dirty` if the worktree had changes.
+    pub commit: Option<String>,
+}
+
+/// Represents benchmark times for a set of days, along with the history of all stored benchmarks.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
     pub data: Vec<Timing>,
+    /// Stored benchmarks, oldest first.
+    pub history: Vec<TimingRecord>,
+    /// The machine of the latest benchmark, see [`environment`](crate::template::environment).
+    pub environment: Option<Environment>,
 }
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, event: Event) -> Result<(), Error> {
+        let path = layout::event_file(event, TIMINGS_FILE_NAME);
+        fs::create_dir_all(layout::data_dir(event))?;
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(path)?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    pub fn read_from_file(event: Event) -> Self {
+        fs::read_to_string(layout::event_file(event, TIMINGS_FILE_NAME))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
     }
 
+    /// Adds the benchmarked parts of `data` to the history.
+    pub fn record(&mut self, timestamp: u64, commit: Option<&str>) {
+        for timing in &self.data {
+            for (part, stats) in [
+                (report::PARSE, &timing.parse_stats),
+                (1, &timing.part_1_stats),
+                (2, &timing.part_2_stats),
+            ] {
+                if let Some(stats) = stats {
+                    self.history.push(TimingRecord {
+                        day: timing.day,
+                        variant: timing.variant.clone(),
+                        part,
+                        stats: stats.clone(),
+                        timestamp,
+                        commit: commit.map(Into::into),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Returns the most recently stored benchmark of a part.
+    pub fn baseline(&self, day: Day, variant: Option<&str>, part: u8) -> Option<&TimingRecord> {
+        self.history
+            .iter()
+            .rev()
+            .find(|r| r.day == day && r.variant.as_deref() == variant && r.part == part)
+    }
+
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// The histories of both are kept.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
@@ -46,13 +153,24 @@ impl Timings {
         }
 
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
+            if !data
+                .iter()
+                .any(|t| t.day == timing.day && t.variant == timing.variant)
+            {
                 data.push(timing.clone());
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by(|a, b| (a.day, &a.variant).cmp(&(b.day, &b.variant)));
+
+        let mut history = self.history.clone();
+        history.extend(new.history.iter().cloned());
+
+        Timings {
+            data,
+            history,
+            environment: new.environment.clone().or_else(|| self.environment.clone()),
+        }
     }
 
     /// Sum up total duration of timings as millis.
@@ -60,10 +178,11 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Whether both parts of the main solution of a day were benched.
     pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+        self.data.iter().any(|t| {
+            t.day == day && t.variant.is_none() && t.part_1.is_some() && t.part_2.is_some()
+        })
     }
 }
 
@@ -78,6 +197,15 @@ impl From<Timings> for JsonValue {
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
+        map.insert(
+            "history".into(),
+            JsonValue::Array(value.history.iter().map(JsonValue::from).collect()),
+        );
+
+        if let Some(environment) = &value.environment {
+            map.insert("environment".into(), JsonValue::from(environment));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -88,19 +216,39 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        // NOTE: the history is optional to support files written before it was recorded.
+        let json_history = match json.get("history") {
+            None => &vec![],
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.history` to be an array.")?,
+        };
+
+        let environment = match json.get("environment") {
+            None => None,
+            Some(v) => Some(Environment::try_from(v)?),
+        };
+
         Ok(Timings {
             data: json_data
                 .iter()
                 .map(Timing::try_from)
                 .collect::<Result<_, _>>()?,
+            history: json_history
+                .iter()
+                .map(TimingRecord::try_from)
+                .collect::<Result<_, _>>()?,
+            environment,
         })
     }
 }
@@ -111,9 +259,15 @@ impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
-        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        day_to_json(&mut map, value.day);
+        variant_to_json(&mut map, value.variant.as_deref());
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        // NOTE: the parse time is only written for two-phase solutions.
+        if let Some(parse) = &value.parse {
+            map.insert("parse".into(), JsonValue::String(parse.clone()));
+        }
+
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
@@ -133,6 +287,47 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        if let Some(stats) = &value.parse_stats {
+            map.insert("parse_stats".into(), JsonValue::from(stats));
+        }
+
+        for (key, stats) in [
+            ("part_1_stats", &value.part_1_stats),
+            ("part_2_stats", &value.part_2_stats),
+        ] {
+            map.insert(
+                key.into(),
+                match stats {
+                    Some(x) => JsonValue::from(x),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        for (key, alloc) in [
+            ("parse_alloc", &value.parse_alloc),
+            ("part_1_alloc", &value.part_1_alloc),
+            ("part_2_alloc", &value.part_2_alloc),
+        ] {
+            if let Some(alloc) = alloc {
+                map.insert(key.into(), JsonValue::from(alloc));
+            }
+        }
+
+        for (key, counters) in [
+            ("parse_counters", &value.parse_counters),
+            ("part_1_counters", &value.part_1_counters),
+            ("part_2_counters", &value.part_2_counters),
+        ] {
+            if let Some(counters) = counters {
+                map.insert(key.into(), JsonValue::from(counters));
+            }
+        }
+
+        parts_to_json(&mut map, "failed", &value.failed);
+        parts_to_json(&mut map, "timeout", &value.timed_out);
+        parts_to_json(&mut map, "oom", &value.out_of_memory);
+
         JsonValue::Object(map)
     }
 }
@@ -145,11 +340,17 @@ impl TryFrom<&JsonValue> for Timing {
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
 
-        let day = json
-            .get("day")
-            .and_then(|v| v.get::<String>())
-            .and_then(|day| Day::from_str(day).ok())
-            .ok_or("Expected timing.day to be a Day struct.")?;
+        let day = day_from_json(json).ok_or("Expected timing.day to be a Day struct.")?;
+        let variant = variant_from_json(json).ok_or("Expected timing.variant to be a string.")?;
+
+        let parse = match json.get("parse") {
+            None => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected timing.parse to be a string.")?
+                    .clone(),
+            ),
+        };
 
         let part_1 = json
             .get("part_1")
@@ -166,48 +367,209 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: statistics are optional to support files written before they were recorded.
+        let read_stats = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => Stats::try_from(v).map(Some),
+        };
+
+        // NOTE: allocations are optional to support files written before they were counted.
+        let read_alloc = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) => AllocStats::try_from(v).map(Some),
+        };
+
+        let read_counters = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) => Counters::try_from(v).map(Some),
+        };
+
+        let failed = parts_from_json(json, "failed")?;
+        let timed_out = parts_from_json(json, "timeout")?;
+        let out_of_memory = parts_from_json(json, "oom")?;
+
         Ok(Timing {
             day,
+            variant,
+            parse,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            parse_stats: read_stats("parse_stats")?,
+            part_1_stats: read_stats("part_1_stats")?,
+            part_2_stats: read_stats("part_2_stats")?,
+            parse_alloc: read_alloc("parse_alloc")?,
+            part_1_alloc: read_alloc("part_1_alloc")?,
+            part_2_alloc: read_alloc("part_2_alloc")?,
+            parse_counters: read_counters("parse_counters")?,
+            part_1_counters: read_counters("part_1_counters")?,
+            part_2_counters: read_counters("part_2_counters")?,
+            failed,
+            timed_out,
+            out_of_memory,
         })
     }
 }
 
+/// Writes a list of parts, only if there are any.
+fn parts_to_json(map: &mut HashMap<String, JsonValue>, key: &str, parts: &[u8]) {
+    if !parts.is_empty() {
+        map.insert(
+            key.into(),
+            JsonValue::Array(
+                parts
+                    .iter()
+                    .map(|&part| JsonValue::Number(f64::from(part)))
+                    .collect(),
+            ),
+        );
+    }
+}
+
+/// Reads a list of parts written by [`parts_to_json`], empty if missing.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn parts_from_json(json: &HashMap<String, JsonValue>, key: &str) -> Result<Vec<u8>, String> {
+    match json.get(key) {
+        None => Ok(vec![]),
+        Some(v) => v
+            .get::<Vec<JsonValue>>()
+            .and_then(|parts| {
+                parts
+                    .iter()
+                    .map(|part| part.get::<f64>().map(|&p| p as u8))
+                    .collect::<Option<Vec<u8>>>()
+            })
+            .ok_or(format!("Expected timing.{key} to be an array of parts.")),
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
-#[cfg(feature = "test_lib")]
-mod tests {
-    use crate::day;
+/// Writes the variant of a solution, omitting it for the main solution.
+fn variant_to_json(map: &mut HashMap<String, JsonValue>, variant: Option<&str>) {
+    if let Some(variant) = variant {
+        map.insert("variant".into(), JsonValue::String(variant.into()));
+    }
+}
+
+/// Reads the optional variant of a solution. Returns `None` if it is not a string.
+fn variant_from_json(json: &HashMap<String, JsonValue>) -> Option<Option<String>> {
+    match json.get("variant") {
+        None => Some(None),
+        Some(v) => v.get::<String>().map(|v| Some(v.clone())),
+    }
+}
+
+impl From<&TimingRecord> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &TimingRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        day_to_json(&mut map, value.day);
+        variant_to_json(&mut map, value.variant.as_deref());
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("stats".into(), JsonValue::from(&value.stats));
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            match &value.commit {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for TimingRecord {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing record to be a JSON object.")?;
+
+        let day = day_from_json(json).ok_or("Expected record.day to be a Day struct.")?;
+        let variant = variant_from_json(json).ok_or("Expected record.variant to be a string.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|&p| p as u8)
+            .ok_or("Expected record.part to be a number.")?;
+
+        let stats = Stats::try_from(
+            json.get("stats")
+                .ok_or("Expected record.stats to be set.")?,
+        )?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|&t| t as u64)
+            .ok_or("Expected record.timestamp to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.commit to be null or string.")?;
 
+        Ok(TimingRecord {
+            day,
+            variant,
+            part,
+            stats,
+            timestamp,
+            commit: commit.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Timings shared by the tests of the modules that read or write them.
+#[cfg(all(test, feature = "test_lib"))]
+pub(crate) mod mock {
     use super::{Timing, Timings};
+    use crate::{day, template::Day};
+
+    /// The timing of a day whose parts took the given times.
+    pub fn mock_timing(
+        day: Day,
+        part_1: Option<&str>,
+        part_2: Option<&str>,
+        total_nanos: f64,
+    ) -> Timing {
+        Timing {
+            part_1: part_1.map(Into::into),
+            part_2: part_2.map(Into::into),
+            total_nanos,
+            ..Timing::new(day)
+        }
+    }
 
-    fn get_mock_timings() -> Timings {
+    /// Timings of days 1 and 2, with both parts solved, and of day 4, with part 1 solved.
+    pub fn mock_timings() -> Timings {
         Timings {
             data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
-                    total_nanos: 4e+10,
-                },
+                mock_timing(day!(1), Some("10ms"), Some("20ms"), 3e+10),
+                mock_timing(day!(2), Some("30ms"), Some("40ms"), 7e+10),
+                mock_timing(day!(4), Some("40ms"), None, 4e+10),
             ],
+            history: vec![],
+            environment: None,
         }
     }
+}
 
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
     mod deserialization {
         use crate::{day, template::timings::Timings};
 
@@ -223,6 +585,34 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_failed_parts() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "failed": [2] }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].failed, vec![2]);
+
+            let json = tinyjson::JsonValue::from(timings.clone())
+                .stringify()
+                .unwrap();
+            let round_tripped = Timings::try_from(json).unwrap();
+            assert_eq!(round_tripped.data[0].failed, vec![2]);
+        }
+
+        #[test]
+        fn handles_parts_over_limits() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": null, "part_2": null, "total_nanos": 0, "timeout": [1], "oom": [2] }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].timed_out, vec![1]);
+            assert_eq!(timings.data[0].out_of_memory, vec![2]);
+
+            let json = tinyjson::JsonValue::from(timings.clone())
+                .stringify()
+                .unwrap();
+            let round_tripped = Timings::try_from(json).unwrap();
+            assert_eq!(round_tripped.data[0].timed_out, vec![1]);
+            assert_eq!(round_tripped.data[0].out_of_memory, vec![2]);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -246,14 +636,14 @@ mod tests {
     }
 
     mod serialization {
-        use super::get_mock_timings;
+        use crate::template::timings::mock::mock_timings;
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
         #[test]
         fn serializes_timings() {
-            let timings = get_mock_timings();
-            let value = JsonValue::try_from(timings).unwrap();
+            let timings = mock_timings();
+            let value = JsonValue::from(timings);
             assert_eq!(
                 value
                     .get::<HashMap<String, JsonValue>>()
@@ -271,70 +661,62 @@ mod tests {
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{Timing, Timings, mock::mock_timing},
         };
 
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
-                }],
+                data: vec![mock_timing(
+                    day!(1),
+                    Some("1ms"),
+                    Some("2ms"),
+                    3_000_000_000_f64,
+                )],
+                history: vec![],
+                environment: None,
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert!(timings.is_day_complete(day!(1)));
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
-                    total_nanos: 1_000_000_000_f64,
-                }],
+                data: vec![mock_timing(day!(1), Some("1ms"), None, 1_000_000_000_f64)],
+                history: vec![],
+                environment: None,
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert!(!timings.is_day_complete(day!(1)));
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
+                data: vec![Timing::new(day!(1))],
+                history: vec![],
+                environment: None,
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert!(!timings.is_day_complete(day!(1)));
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::environment::Environment,
+            template::timings::{Timing, Timings, mock::mock_timings},
         };
 
-        use super::get_mock_timings;
-
         #[test]
         fn handles_disjunct_timings() {
-            let timings = get_mock_timings();
+            let timings = mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(3))],
+                history: vec![],
+                environment: None,
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -346,15 +728,12 @@ mod tests {
 
         #[test]
         fn handles_overlapping_timings() {
-            let timings = get_mock_timings();
+            let timings = mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(2))],
+                history: vec![],
+                environment: None,
             };
             let merged = timings.merge(&other);
 
@@ -368,17 +747,180 @@ mod tests {
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
-            let other = get_mock_timings();
+            let other = mock_timings();
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
 
         #[test]
         fn handles_empty_other_timings() {
-            let timings = get_mock_timings();
+            let timings = mock_timings();
             let other = Timings::default();
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn keeps_latest_environment() {
+            let environment = |os: &str| {
+                Some(Environment {
+                    os: os.into(),
+                    ..Environment::default()
+                })
+            };
+            let timings = Timings {
+                environment: environment("linux"),
+                ..mock_timings()
+            };
+
+            let merged = timings.merge(&Timings::default());
+            assert_eq!(merged.environment, environment("linux"));
+
+            let other = Timings {
+                environment: environment("macos"),
+                ..Timings::default()
+            };
+            let merged = timings.merge(&other);
+            assert_eq!(merged.environment, environment("macos"));
+
+            let json = tinyjson::JsonValue::from(merged.clone())
+                .stringify()
+                .unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.environment, merged.environment);
+        }
+    }
+
+    mod history {
+        use crate::{
+            day,
+            template::{
+                alloc::AllocStats,
+                counters::Counters,
+                stats::Stats,
+                timings::{Timing, Timings},
+            },
+        };
+
+        fn bench(day: crate::template::Day, median: f64) -> Timings {
+            let stats = Stats {
+                samples: 10,
+                min: median,
+                median,
+                mean: median,
+                p95: median,
+                std_dev: 0.0,
+                outliers: 0,
+            };
+
+            Timings {
+                data: vec![Timing {
+                    part_1: Some(format!("{median}ns")),
+                    total_nanos: median,
+                    part_1_stats: Some(stats),
+                    ..Timing::new(day)
+                }],
+                history: vec![],
+                environment: None,
+            }
+        }
+
+        #[test]
+        fn records_benchmarked_parts() {
+            let mut timings = bench(day!(1), 100.0);
+            timings.record(1000, Some("abc1234"));
+
+            assert_eq!(timings.history.len(), 1);
+            assert_eq!(timings.history[0].part, 1);
+            assert_eq!(timings.history[0].commit.as_deref(), Some("abc1234"));
+        }
+
+        #[test]
+        fn keeps_history_when_merging() {
+            let mut first = bench(day!(1), 100.0);
+            first.record(1000, Some("abc1234"));
+            let mut second = bench(day!(1), 200.0);
+            second.record(2000, None);
+
+            let merged = first.merge(&second);
+            assert_eq!(merged.data.len(), 1);
+            assert_eq!(merged.history.len(), 2);
+
+            let baseline = merged.baseline(day!(1), None, 1).unwrap();
+            assert_eq!(baseline.timestamp, 2000);
+            assert_eq!(baseline.stats.median, 200.0);
+            assert!(merged.baseline(day!(1), None, 2).is_none());
+        }
+
+        #[test]
+        fn round_trips_history() {
+            let mut timings = bench(day!(1), 100.0);
+            timings.record(1000, Some("abc1234-dirty"));
+
+            let json = tinyjson::JsonValue::from(timings.clone())
+                .stringify()
+                .unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.history, timings.history);
+        }
+
+        #[test]
+        fn round_trips_allocations() {
+            let mut timings = bench(day!(4), 100.0);
+            timings.data[0].part_1_alloc = Some(AllocStats::default());
+            timings.data[0].part_2_alloc = Some(AllocStats {
+                allocations: 2,
+                bytes: 4096,
+                peak_bytes: 2048,
+            });
+
+            let json = tinyjson::JsonValue::from(timings.clone())
+                .stringify()
+                .unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].parse_alloc, None);
+            assert_eq!(parsed.data[0].part_1_alloc, timings.data[0].part_1_alloc);
+            assert_eq!(parsed.data[0].part_2_alloc, timings.data[0].part_2_alloc);
+        }
+
+        #[test]
+        fn round_trips_counters() {
+            let mut timings = bench(day!(7), 100.0);
+            timings.data[0].part_1_counters = Some(Counters {
+                instructions: Some(5000.0),
+                cycles: Some(2000.0),
+                branches: Some(800.0),
+                branch_misses: Some(4.0),
+                ..Counters::default()
+            });
+
+            let json = tinyjson::JsonValue::from(timings.clone())
+                .stringify()
+                .unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(
+                parsed.data[0].part_1_counters,
+                timings.data[0].part_1_counters
+            );
+            assert_eq!(parsed.data[0].part_2_counters, None);
+        }
+
+        #[test]
+        fn round_trips_parse_times() {
+            let mut timings = bench(day!(8), 100.0);
+            timings.data[0].parse = Some("2.0ms".into());
+            timings.data[0].parse_stats = timings.data[0].part_1_stats.clone();
+            timings.record(1000, None);
+
+            assert_eq!(timings.history.len(), 2);
+            assert_eq!(timings.history[0].part, 0);
+
+            let json = tinyjson::JsonValue::from(timings.clone())
+                .stringify()
+                .unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].parse.as_deref(), Some("2.0ms"));
+            assert_eq!(parsed.data[0].parse_stats, timings.data[0].parse_stats);
+        }
     }
 }
@@ -0,0 +1,9 @@
+//! Checks that all variants of a solution agree, see [`advent_of_code::template::differential`].
+use advent_of_code::template::differential::{self, Candidate};
+
+include!(concat!(env!("OUT_DIR"), "/variants.rs"));
+
+#[test]
+fn variants_agree() {
+    differential::assert_agree(CANDIDATES);
+}